@@ -11,36 +11,79 @@ pub fn generate_ast(output_dir: &String) -> io::Result<()> {
     define_ast(
         output_dir,
         &"Expr".to_string(),
-        vec!["error_handler::ViskumError", "token::Token", "token::Literal"],
+        vec![
+            "crate::error_handler::ViskumError",
+            "crate::token::Token",
+            "crate::token::Literal",
+            "crate::stmt::Stmt",
+            "crate::stmt::FunctionStmt",
+            "crate::pattern::MatchArm",
+            "crate::viskum_function::NamedArgument",
+            "std::cell::Cell",
+            "std::rc::Rc"
+        ],
         vec![
             "Binary   : left: Box<Expr>, operator: Token, right: Box<Expr>",
-            "Call     : callee: Box<Expr>, paren: Token, arguments: Vec<Expr>",
+            "Call     : callee: Box<Expr>, paren: Token, arguments: Vec<Expr>, named: Vec<NamedArgument>",
+            "Partial  : callee: Box<Expr>, paren: Token, arguments: Vec<Expr>, named: Vec<NamedArgument>",
+            "Placeholder : token: Token",
             "Grouping : expression: Box<Expr>",
             "Literal  : value: Option<Literal>",
             "Logical  : left: Box<Expr>, operator: Token, right: Box<Expr>",
             "Prefix   : operator: Token, right: Box<Expr>",
             "Postfix  : left: Box<Expr>, operator: Token",
             "Ternary  : condition: Box<Expr>, true_expr: Box<Expr>, false_expr: Box<Expr>",
-            "Variable : token: Token",
-            "Assign   : token: Token, assignment_token: Token, value: Box<Expr>"
+            "Variable : token: Token, depth: Cell<Option<usize>>",
+            "Assign   : token: Token, assignment_token: Token, value: Box<Expr>, depth: Cell<Option<usize>>, postfix: bool",
+            "Get      : object: Box<Expr>, token: Token",
+            "Set      : object: Box<Expr>, token: Token, assignment_token: Token, value: Box<Expr>, postfix: bool",
+            "This     : keyword: Token, depth: Cell<Option<usize>>",
+            "New      : keyword: Token, class: Box<Expr>, paren: Token, arguments: Vec<Expr>, named: Vec<NamedArgument>",
+            "List     : elements: Vec<Expr>",
+            "Tuple    : elements: Vec<Expr>",
+            "Map      : bracket: Token, keys: Vec<Expr>, values: Vec<Expr>",
+            "Index    : object: Box<Expr>, bracket: Token, index: Box<Expr>",
+            "IndexSet : object: Box<Expr>, bracket: Token, index: Box<Expr>, assignment_token: Token, value: Box<Expr>, postfix: bool",
+            "Lambda   : declaration: FunctionStmt",
+            "Interpolation : parts: Vec<String>, expressions: Vec<Expr>",
+            "Match    : keyword: Token, subject: Box<Expr>, arms: Vec<MatchArm>",
+            "Range    : start: Box<Expr>, operator: Token, end: Box<Expr>, step: Option<Box<Expr>>",
+            "Block    : statements: Vec<Stmt>, value: Option<Box<Expr>>",
+            "If       : condition: Box<Expr>, then_branch: Box<Expr>, else_branch: Option<Box<Expr>>",
+            "Loop     : label: Option<Token>, body: Rc<Stmt>"
         ]
     )?;
 
     define_ast(
         output_dir,
         &"Stmt".to_string(),
-        vec!["error_handler::ViskumError", "token::Token", "expr::Expr"],
         vec![
-            "Block       : statements: Vec<Stmt>",
+            "crate::error_handler::ViskumError",
+            "crate::token::Token",
+            "crate::expr::Expr",
+            "crate::viskum_enum::VariantDeclaration",
+            "crate::viskum_function::Parameter",
+            "std::rc::Rc"
+        ],
+        vec![
+            "Block       : statements: Rc<[Stmt]>",
             "Expression  : expression: Expr",
-            "If          : condition: Expr, then_branch: Box<Stmt>, else_branch: Option<Box<Stmt>>",
+            "If          : condition: Expr, then_branch: Rc<Stmt>, else_branch: Option<Rc<Stmt>>",
             "Print       : expression: Expr",
-            "Let         : token: Token, initializer: Expr",
-            "While       : condition: Expr, body: Box<Stmt>",
-            "LoopControl : keyword: Token",
-            "Loop        : body: Box<Stmt>",
-            "Function    : token: Token, params: Vec<Token>, body: Vec<Stmt>",
-            "Return      : keyword: Token, value: Option<Expr>"
+            "Let         : token: Token, initializer: Expr, mutable: bool",
+            "Destructure : keyword: Token, names: Vec<Token>, initializer: Expr, mutable: bool",
+            "While       : label: Option<Token>, condition: Expr, body: Rc<Stmt>",
+            "For         : label: Option<Token>, initializer: Option<Box<Stmt>>, condition: Option<Expr>, increment: Option<Expr>, body: Rc<Stmt>",
+            "ForIn       : label: Option<Token>, token: Token, iterable: Expr, body: Rc<Stmt>",
+            "LoopControl : keyword: Token, label: Option<Token>, value: Option<Expr>",
+            "Function    : token: Token, params: Vec<Parameter>, body: Rc<[Stmt]>, generator: bool",
+            "Class       : token: Token, methods: Vec<FunctionStmt>",
+            "Enum        : token: Token, variants: Vec<VariantDeclaration>",
+            "Import      : keyword: Token, path: String, name: Token",
+            "Return      : keyword: Token, value: Option<Expr>",
+            "Yield       : keyword: Token, value: Option<Expr>",
+            "Throw       : keyword: Token, value: Expr",
+            "Try         : body: Vec<Stmt>, catch_name: Option<Token>, catch_body: Option<Vec<Stmt>>, finally_body: Option<Vec<Stmt>>"
         ]
     )?;
 
@@ -50,7 +93,7 @@ pub fn generate_ast(output_dir: &String) -> io::Result<()> {
 fn define_ast(
     output_dir: &String,
     base_name: &String,
-    crates: Vec<&str>,
+    imports: Vec<&str>,
     types: Vec<&str>
 ) -> io::Result<()> {
     let base_name_lowercase = base_name.to_lowercase();
@@ -59,8 +102,8 @@ fn define_ast(
     let mut tree_types: Vec<TreeType> = Vec::new();
     let return_type = "Result<T, ViskumError>";
 
-    for crate_ in crates {
-        writeln!(file, "use crate::{};", crate_)?;
+    for import in imports {
+        writeln!(file, "use {};", import)?;
     }
 
     writeln!(file)?;
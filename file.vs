@@ -15,7 +15,7 @@ let a = 8!;
 let b = 16!;
 let c = 24!;
 
-let result = "No match";
+let mut result = "No match";
 
 if 1 + 2 == 2 {
   result = "first";
@@ -29,7 +29,7 @@ if 1 + 2 == 2 {
 print "string";
 
 
-let count = 0;
+let mut count = 0;
 
 while count < 10 {
   count = count + 1;
@@ -100,8 +100,190 @@ impl ExprVisitor<Output> for AstPrinter {
             str_builder = format!("{str_builder} {}", arg.accept(self)?);
         }
 
+        for arg in &expr.named {
+            str_builder = format!("{str_builder} {}: {}", arg.token.lexeme, arg.value.accept(self)?);
+        }
+
         str_builder = format!("{str_builder})");
 
         Ok(str_builder)
     }
-}
+
+    fn visit_partial_expr(&self, expr: &PartialExpr) -> Result<Output, ViskumError> {
+        let mut str_builder = format!("(partial {} ", expr.callee.accept(self)?);
+
+        for arg in &expr.arguments {
+            str_builder = format!("{str_builder} {}", arg.accept(self)?);
+        }
+
+        for arg in &expr.named {
+            str_builder = format!("{str_builder} {}: {}", arg.token.lexeme, arg.value.accept(self)?);
+        }
+
+        str_builder = format!("{str_builder})");
+
+        Ok(str_builder)
+    }
+
+    fn visit_placeholder_expr(&self, _expr: &PlaceholderExpr) -> Result<Output, ViskumError> {
+        Ok("_".to_string())
+    }
+
+    fn visit_get_expr(&self, expr: &GetExpr) -> Result<Output, ViskumError> {
+        Ok(format!("(get {} {})", expr.object.accept(self)?, expr.token.lexeme))
+    }
+
+    fn visit_set_expr(&self, expr: &SetExpr) -> Result<Output, ViskumError> {
+        Ok(
+            format!(
+                "(set {} {} {})",
+                expr.object.accept(self)?,
+                expr.token.lexeme,
+                expr.value.accept(self)?
+            )
+        )
+    }
+
+    fn visit_this_expr(&self, _expr: &ThisExpr) -> Result<Output, ViskumError> {
+        Ok("this".to_string())
+    }
+
+    fn visit_new_expr(&self, expr: &NewExpr) -> Result<Output, ViskumError> {
+        let mut str_builder = format!("(new {}", expr.class.accept(self)?);
+
+        for arg in &expr.arguments {
+            str_builder = format!("{str_builder} {}", arg.accept(self)?);
+        }
+
+        str_builder = format!("{str_builder})");
+
+        Ok(str_builder)
+    }
+
+    fn visit_list_expr(&self, expr: &ListExpr) -> Result<Output, ViskumError> {
+        let mut str_builder = "(list".to_string();
+
+        for element in &expr.elements {
+            str_builder = format!("{str_builder} {}", element.accept(self)?);
+        }
+
+        str_builder = format!("{str_builder})");
+
+        Ok(str_builder)
+    }
+
+    fn visit_tuple_expr(&self, expr: &TupleExpr) -> Result<Output, ViskumError> {
+        let mut str_builder = "(tuple".to_string();
+
+        for element in &expr.elements {
+            str_builder = format!("{str_builder} {}", element.accept(self)?);
+        }
+
+        str_builder = format!("{str_builder})");
+
+        Ok(str_builder)
+    }
+
+    fn visit_map_expr(&self, expr: &MapExpr) -> Result<Output, ViskumError> {
+        let mut str_builder = "(map".to_string();
+
+        for (key, value) in expr.keys.iter().zip(expr.values.iter()) {
+            str_builder = format!("{str_builder} ({} {})", key.accept(self)?, value.accept(self)?);
+        }
+
+        str_builder = format!("{str_builder})");
+
+        Ok(str_builder)
+    }
+
+    fn visit_index_expr(&self, expr: &IndexExpr) -> Result<Output, ViskumError> {
+        self.parenthesize(&"index".to_string(), &vec![&expr.object, &expr.index])
+    }
+
+    fn visit_indexset_expr(&self, expr: &IndexSetExpr) -> Result<Output, ViskumError> {
+        self.parenthesize(
+            &format!("index{}", expr.assignment_token.lexeme),
+            &vec![&expr.object, &expr.index, &expr.value]
+        )
+    }
+
+    fn visit_lambda_expr(&self, expr: &LambdaExpr) -> Result<Output, ViskumError> {
+        let params: Vec<String> = expr.declaration.params
+            .iter()
+            .map(|param| param.token.lexeme.clone())
+            .collect();
+
+        Ok(format!("(lambda ({}))", params.join(" ")))
+    }
+
+    fn visit_interpolation_expr(&self, expr: &InterpolationExpr) -> Result<Output, ViskumError> {
+        let mut str_builder = format!("(interpolation \"{}\"", expr.parts[0]);
+
+        for (expression, part) in expr.expressions.iter().zip(&expr.parts[1..]) {
+            str_builder = format!("{str_builder} {} \"{}\"", expression.accept(self)?, part);
+        }
+
+        str_builder = format!("{str_builder})");
+
+        Ok(str_builder)
+    }
+
+    fn visit_match_expr(&self, expr: &MatchExpr) -> Result<Output, ViskumError> {
+        let mut str_builder = format!("(match {}", expr.subject.accept(self)?);
+
+        for arm in &expr.arms {
+            str_builder = format!("{str_builder} ({}", arm.pattern);
+
+            if let Some(guard) = &arm.guard {
+                str_builder = format!("{str_builder} if {}", guard.accept(self)?);
+            }
+
+            str_builder = format!("{str_builder} => {})", arm.body.accept(self)?);
+        }
+
+        str_builder = format!("{str_builder})");
+
+        Ok(str_builder)
+    }
+
+    fn visit_block_expr(&self, expr: &BlockExpr) -> Result<Output, ViskumError> {
+        match &expr.value {
+            Some(value) => Ok(format!("(block {})", value.accept(self)?)),
+            None => Ok("(block)".to_string()),
+        }
+    }
+
+    fn visit_if_expr(&self, expr: &IfExpr) -> Result<Output, ViskumError> {
+        let str_builder = format!(
+            "(if {} {}",
+            expr.condition.accept(self)?,
+            expr.then_branch.accept(self)?
+        );
+
+        match &expr.else_branch {
+            Some(else_branch) => Ok(format!("{str_builder} {})", else_branch.accept(self)?)),
+            None => Ok(format!("{str_builder})")),
+        }
+    }
+
+    fn visit_loop_expr(&self, expr: &LoopExpr) -> Result<Output, ViskumError> {
+        match &expr.label {
+            Some(label) => Ok(format!("(loop {})", label.lexeme)),
+            None => Ok("(loop)".to_string()),
+        }
+    }
+
+    fn visit_range_expr(&self, expr: &RangeExpr) -> Result<Output, ViskumError> {
+        let range = format!(
+            "({} {} {}",
+            expr.operator.lexeme,
+            expr.start.accept(self)?,
+            expr.end.accept(self)?
+        );
+
+        match &expr.step {
+            Some(step) => Ok(format!("{range} step {})", step.accept(self)?)),
+            None => Ok(format!("{range})")),
+        }
+    }
+}
\ No newline at end of file
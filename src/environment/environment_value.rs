@@ -4,15 +4,32 @@ use crate::token::Literal;
 pub struct EnvironmentValue {
     value: Literal,
     mutable: bool,
+    line: usize,
     // value_type: String
 }
 
 impl EnvironmentValue {
     pub fn new(value: Literal, mutable: bool) -> Self {
-        EnvironmentValue { value, mutable }
+        EnvironmentValue { value, mutable, line: 0 }
+    }
+
+    pub fn declared_at(self, line: usize) -> Self {
+        EnvironmentValue { line, ..self }
     }
 
     pub fn get_value(&self) -> Literal {
         self.value.clone()
     }
+
+    pub fn set_value(&mut self, value: Literal) {
+        self.value = value;
+    }
+
+    pub fn is_mutable(&self) -> bool {
+        self.mutable
+    }
+
+    pub fn get_line(&self) -> usize {
+        self.line
+    }
 }
@@ -1,8 +1,17 @@
 use std::{ rc::Rc, collections::HashMap };
 
-use crate::{ token::Literal, viskum_callable::Callable };
+use crate::{ token::Literal, viskum_callable::{ Callable, Arity } };
 
-use super::{ environment_value::EnvironmentValue, native_functions::time::NativeClock };
+use super::{
+    environment_value::EnvironmentValue,
+    native_functions::{
+        time::NativeClock,
+        len::NativeLen,
+        push::NativePush,
+        tag_of::NativeTagOf,
+        iter::NativeIter,
+    },
+};
 
 pub fn get_globals() -> HashMap<String, EnvironmentValue> {
     let mut globals: HashMap<String, EnvironmentValue> = HashMap::new();
@@ -10,7 +19,7 @@ pub fn get_globals() -> HashMap<String, EnvironmentValue> {
     globals.insert(
         "time".to_string(),
         EnvironmentValue::new(
-            Literal::Func(Callable { arity: 0, func: Rc::new(NativeClock {}) }),
+            Literal::Func(Callable { arity: Arity::exactly(0), func: Rc::new(NativeClock {}) }),
             false
         )
     );
@@ -18,7 +27,39 @@ pub fn get_globals() -> HashMap<String, EnvironmentValue> {
     globals.insert(
         "new_print".to_string(),
         EnvironmentValue::new(
-            Literal::Func(Callable { arity: 0, func: Rc::new(NativeClock {}) }),
+            Literal::Func(Callable { arity: Arity::exactly(0), func: Rc::new(NativeClock {}) }),
+            false
+        )
+    );
+
+    globals.insert(
+        "len".to_string(),
+        EnvironmentValue::new(
+            Literal::Func(Callable { arity: Arity::exactly(1), func: Rc::new(NativeLen {}) }),
+            false
+        )
+    );
+
+    globals.insert(
+        "push".to_string(),
+        EnvironmentValue::new(
+            Literal::Func(Callable { arity: Arity::exactly(2), func: Rc::new(NativePush {}) }),
+            false
+        )
+    );
+
+    globals.insert(
+        "tag_of".to_string(),
+        EnvironmentValue::new(
+            Literal::Func(Callable { arity: Arity::exactly(1), func: Rc::new(NativeTagOf {}) }),
+            false
+        )
+    );
+
+    globals.insert(
+        "iter".to_string(),
+        EnvironmentValue::new(
+            Literal::Func(Callable { arity: Arity::exactly(1), func: Rc::new(NativeIter {}) }),
             false
         )
     );
@@ -15,16 +15,57 @@ pub struct Environment {
 }
 
 impl Environment {
+    /// The top-level environment of a file. The native functions are in the
+    /// environment around it, so they aren't part of what a module exports
     pub fn new() -> Self {
-        let globals = globals::get_globals();
+        let globals = Environment { values: globals::get_globals(), enclosing: None };
 
-        Environment { values: globals, enclosing: None }
+        Environment::new_with_enclosing(Rc::new(RefCell::new(globals)))
     }
 
     pub fn new_with_enclosing(environment: Rc<RefCell<Environment>>) -> Self {
         Environment { values: HashMap::new(), enclosing: Some(environment) }
     }
 
+    pub fn ancestor(
+        environment: &Rc<RefCell<Environment>>,
+        distance: usize
+    ) -> Rc<RefCell<Environment>> {
+        let mut environment = environment.clone();
+
+        for _ in 0..distance {
+            let enclosing = environment.borrow().enclosing.clone();
+            match enclosing {
+                Some(enclosing) => {
+                    environment = enclosing;
+                }
+                None => {
+                    break;
+                }
+            }
+        }
+
+        environment
+    }
+
+    /// The top-level environment of the file, which is the one right inside
+    /// of the native functions
+    pub fn root(environment: &Rc<RefCell<Environment>>) -> Rc<RefCell<Environment>> {
+        let mut environment = environment.clone();
+
+        loop {
+            let enclosing = environment.borrow().enclosing.clone();
+            match enclosing {
+                Some(enclosing) if enclosing.borrow().enclosing.is_some() => {
+                    environment = enclosing;
+                }
+                _ => {
+                    return environment;
+                }
+            }
+        }
+    }
+
     pub fn get(&self, token: &Token) -> Result<Literal, ViskumError> {
         if let Some(literal) = self.values.get(&token.lexeme) {
             Ok(literal.get_value())
@@ -34,13 +75,18 @@ impl Environment {
             Err(
                 ViskumError::new(
                     format!("Undefined variable '{}'", token.lexeme).as_str(),
-                    token.clone(),
-                    "file.vs"
+                    token.clone()
                 )
             )
         }
     }
 
+    /// Looks up a variable declared in this environment, and not in the ones
+    /// around it
+    pub fn get_local(&self, name: &str) -> Option<Literal> {
+        self.values.get(name).map(|value| value.get_value())
+    }
+
     pub fn define_function(&mut self, function_name: &str, viskum_callable: Literal) {
         self.values.insert(
             function_name.to_string(),
@@ -53,26 +99,39 @@ impl Environment {
         token: &Token,
         environment_value: EnvironmentValue
     ) -> Result<Literal, ViskumError> {
-        self.values.insert(token.lexeme.clone(), environment_value.clone());
+        let value = environment_value.get_value();
+
+        self.values.insert(token.lexeme.clone(), environment_value.declared_at(token.line));
 
-        Ok(environment_value.get_value())
+        Ok(value)
     }
 
-    pub fn assign(
-        &mut self,
-        token: &Token,
-        environment_value: EnvironmentValue
-    ) -> Result<Literal, ViskumError> {
-        if self.values.contains_key(&token.lexeme) {
-            self.define(token, environment_value)
+    pub fn assign(&mut self, token: &Token, value: Literal) -> Result<Literal, ViskumError> {
+        if let Some(environment_value) = self.values.get_mut(&token.lexeme) {
+            if !environment_value.is_mutable() {
+                let msg = if environment_value.get_line() == 0 {
+                    format!("Cannot assign to '{}'", token.lexeme)
+                } else {
+                    format!(
+                        "Cannot assign to immutable variable '{}' declared on line {}. Declare it with 'let mut' to make it mutable",
+                        token.lexeme,
+                        environment_value.get_line()
+                    )
+                };
+
+                return Err(ViskumError::new(msg.as_str(), token.clone()));
+            }
+
+            environment_value.set_value(value.clone());
+
+            Ok(value)
         } else if let Some(enclosing) = &self.enclosing {
-            Ok(enclosing.borrow_mut().assign(token, environment_value)?)
+            Ok(enclosing.borrow_mut().assign(token, value)?)
         } else {
             Err(
                 ViskumError::new(
                     format!("Undefined variable '{}'", token.lexeme).as_str(),
-                    token.clone(),
-                    "file.vs"
+                    token.clone()
                 )
             )
         }
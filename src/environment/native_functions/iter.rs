@@ -0,0 +1,29 @@
+use crate::{
+    viskum_callable::{ ViskumCallable, Arity },
+    token::{ Literal, Token },
+    error_handler::ViskumError,
+    interpreter::Interpreter,
+};
+
+/// Gives the iterator a for-in loop would go through, e.g. iter([1, 2]).next()
+pub struct NativeIter;
+
+impl ViskumCallable for NativeIter {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        arguments: &Vec<Literal>
+    ) -> Result<Literal, ViskumError> {
+        let iterator = interpreter.iterator(&arguments[0], &Token::invalid(None))?;
+
+        Ok(Literal::Iterator(iterator))
+    }
+
+    fn arity(&self) -> Arity {
+        Arity::exactly(1)
+    }
+
+    fn to_string(&self) -> String {
+        "<native fn iter>".to_string()
+    }
+}
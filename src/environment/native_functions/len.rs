@@ -0,0 +1,49 @@
+use crate::{
+    viskum_callable::{ ViskumCallable, Arity },
+    token::{ Literal, Token },
+    error_handler::ViskumError,
+    interpreter::Interpreter,
+};
+
+pub struct NativeLen;
+
+impl ViskumCallable for NativeLen {
+    fn call(
+        &self,
+        _interpreter: &Interpreter,
+        arguments: &Vec<Literal>
+    ) -> Result<Literal, ViskumError> {
+        match &arguments[0] {
+            Literal::Str(str) => Ok(Literal::Int(str.chars().count() as i64)),
+            Literal::List(list) => Ok(Literal::Int(list.borrow().len() as i64)),
+            Literal::Tuple(tuple) => Ok(Literal::Int(tuple.len() as i64)),
+            Literal::Map(map) => Ok(Literal::Int(map.borrow().len() as i64)),
+            Literal::Range(range) =>
+                match i64::try_from(range.len()) {
+                    Ok(len) => Ok(Literal::Int(len)),
+                    Err(_) =>
+                        Err(
+                            ViskumError::new(
+                                format!("Integer overflow: The range {} has too many values for an int", range).as_str(),
+                                Token::invalid(None)
+                            )
+                        ),
+                }
+            value =>
+                Err(
+                    ViskumError::new(
+                        format!("len() is not defined for {}", value.to_type_string()).as_str(),
+                        Token::invalid(None)
+                    )
+                ),
+        }
+    }
+
+    fn arity(&self) -> Arity {
+        Arity::exactly(1)
+    }
+
+    fn to_string(&self) -> String {
+        "<native fn len>".to_string()
+    }
+}
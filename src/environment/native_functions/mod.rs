@@ -1,2 +1,6 @@
 pub mod time;
 pub mod print;
+pub mod len;
+pub mod push;
+pub mod tag_of;
+pub mod iter;
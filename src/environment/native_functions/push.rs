@@ -0,0 +1,38 @@
+use crate::{
+    viskum_callable::{ ViskumCallable, Arity },
+    token::{ Literal, Token },
+    error_handler::ViskumError,
+    interpreter::Interpreter,
+};
+
+pub struct NativePush;
+
+impl ViskumCallable for NativePush {
+    fn call(
+        &self,
+        _interpreter: &Interpreter,
+        arguments: &Vec<Literal>
+    ) -> Result<Literal, ViskumError> {
+        match &arguments[0] {
+            Literal::List(list) => {
+                list.borrow_mut().push(arguments[1].clone());
+                Ok(Literal::Null)
+            }
+            value =>
+                Err(
+                    ViskumError::new(
+                        format!("Cannot push to a {}", value.to_type_string()).as_str(),
+                        Token::invalid(None)
+                    )
+                ),
+        }
+    }
+
+    fn arity(&self) -> Arity {
+        Arity::exactly(2)
+    }
+
+    fn to_string(&self) -> String {
+        "<native fn push>".to_string()
+    }
+}
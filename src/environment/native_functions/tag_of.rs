@@ -0,0 +1,36 @@
+use crate::{
+    viskum_callable::{ ViskumCallable, Arity },
+    token::{ Literal, Token },
+    error_handler::ViskumError,
+    interpreter::Interpreter,
+};
+
+/// Gives the name of an enum variant, e.g. "Circle" for Shape.Circle(2)
+pub struct NativeTagOf;
+
+impl ViskumCallable for NativeTagOf {
+    fn call(
+        &self,
+        _interpreter: &Interpreter,
+        arguments: &Vec<Literal>
+    ) -> Result<Literal, ViskumError> {
+        match &arguments[0] {
+            Literal::Variant(variant) => Ok(Literal::Str(variant.tag().to_string())),
+            value =>
+                Err(
+                    ViskumError::new(
+                        format!("tag_of() is not defined for {}", value.to_type_string()).as_str(),
+                        Token::invalid(None)
+                    )
+                ),
+        }
+    }
+
+    fn arity(&self) -> Arity {
+        Arity::exactly(1)
+    }
+
+    fn to_string(&self) -> String {
+        "<native fn tag_of>".to_string()
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    viskum_callable::ViskumCallable,
+    viskum_callable::{ ViskumCallable, Arity },
     token::Literal,
     error_handler::ViskumError,
     interpreter::Interpreter,
@@ -24,8 +24,8 @@ impl ViskumCallable for NativeClock {
         )
     }
 
-    fn arity(&self) -> usize {
-        0
+    fn arity(&self) -> Arity {
+        Arity::exactly(0)
     }
 }
 
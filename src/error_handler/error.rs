@@ -1,53 +1,76 @@
+use std::rc::Rc;
+
 use colorize::{ self, AnsiColor };
 
-use crate::token::{ Token, Literal };
+use crate::{
+    token::{ Token, TokenType, Literal },
+    viskum_callable::Callable,
+    viskum_exception::ViskumException,
+};
 
-#[derive(Debug, PartialEq)]
+/// Break and continue carry the label of the loop they're for, if they
+/// name one. A tail call carries the token of the call it came from
+#[derive(Debug)]
 pub enum AbortReason {
-    Break,
-    Continue,
+    Break(Option<String>, Literal),
+    Continue(Option<String>),
     Return(Literal),
+    TailCall(Callable, Vec<Literal>, Token),
+    Throw(Literal),
+}
+
+/// What a loop does after its body was left with break or continue
+pub enum LoopControl {
+    Break(Literal),
+    Continue,
 }
 
 #[derive(Debug)]
 pub struct ViskumError {
     msg: String,
     token: Token,
-    file: String,
-    abort_reason: Option<AbortReason>,
-    abort_value: Option<Literal>,
+    // Boxed so the errors every interpreter call returns stay small
+    abort_reason: Option<Box<AbortReason>>,
 }
 
 impl ViskumError {
-    pub fn new(msg: &str, token: Token, file: &str) -> Self {
+    pub fn new(msg: &str, token: Token) -> Self {
         ViskumError {
             msg: msg.to_string(),
             token,
-            file: file.to_string(),
             abort_reason: None,
-            abort_value: None,
         }
     }
-    pub fn new_with_abort(msg: &str, token: Token, file: &str, reason: AbortReason) -> Self {
+    pub fn new_with_abort(msg: &str, token: Token, reason: AbortReason) -> Self {
         ViskumError {
             msg: msg.to_string(),
             token,
-            file: file.to_string(),
-            abort_reason: Some(reason),
-            abort_value: None,
+            abort_reason: Some(Box::new(reason)),
         }
     }
 
-    pub fn is_abort_error(&self, abort_reason: AbortReason) -> bool {
-        match &self.abort_reason {
-            Some(AbortReason::Return(_)) => true,
-            Some(reason) => reason == &abort_reason,
-            None => false,
+    /// A break or continue is for the loop with the given label, or the
+    /// innermost loop if it doesn't name one. Anything else is passed on
+    pub fn into_loop_control(self, label: Option<&Token>) -> Result<LoopControl, ViskumError> {
+        let is_for_loop = |target: &Option<String>| {
+            match (target, label) {
+                (None, _) => true,
+                (Some(target), Some(label)) => target == &label.lexeme,
+                (Some(_), None) => false,
+            }
+        };
+
+        match self.abort_reason.as_deref() {
+            Some(AbortReason::Break(target, value)) if is_for_loop(target) =>
+                Ok(LoopControl::Break(value.clone())),
+            Some(AbortReason::Continue(target)) if is_for_loop(target) =>
+                Ok(LoopControl::Continue),
+            _ => Err(self),
         }
     }
 
     pub fn get_abort_value(&self) -> Option<Literal> {
-        self.abort_reason.as_ref().and_then(|reason| {
+        self.abort_reason.as_deref().and_then(|reason| {
             match reason {
                 AbortReason::Return(value) => Some(value.clone()),
                 _ => None,
@@ -55,10 +78,56 @@ impl ViskumError {
         })
     }
 
+    /// Native functions don't know where they were called from, so their
+    /// errors are attributed to the call site
+    pub fn with_call_site(mut self, token: &Token) -> Self {
+        if self.token.is(TokenType::Invalid) {
+            self.token = token.clone();
+        }
+        self
+    }
+
+    /// Errors from tokens that weren't scanned from a file, like the ones the
+    /// lexer reports, belong to the file being run
+    pub fn in_file(mut self, file: &str) -> Self {
+        if self.token.file.is_empty() {
+            self.token.file = Rc::from(file);
+        }
+        self
+    }
+
+    /// Thrown values and errors from the interpreter can be caught, while
+    /// break, continue and return can't
+    pub fn into_exception(self) -> Result<Literal, ViskumError> {
+        match self.abort_reason.as_deref() {
+            Some(AbortReason::Throw(exception)) => Ok(exception.clone()),
+            None => Ok(ViskumException::new_literal(self.msg, self.token.line, Literal::Null)),
+            _ => Err(self),
+        }
+    }
+
+    pub fn into_tail_call(self) -> Result<(Callable, Vec<Literal>, Token), ViskumError> {
+        let reason = match self.abort_reason {
+            Some(reason) => *reason,
+            None => {
+                return Err(self);
+            }
+        };
+
+        match reason {
+            AbortReason::TailCall(callable, arguments, token) => Ok((callable, arguments, token)),
+            reason =>
+                Err(ViskumError {
+                    abort_reason: Some(Box::new(reason)),
+                    ..self
+                }),
+        }
+    }
+
     pub fn to_string(&self) -> String {
         let p1 = "[error]".red().bold();
 
-        let p2 = vec![self.file.to_string(), ":".to_string()].join("").red();
+        let p2 = vec![self.token.file.to_string(), ":".to_string()].join("").red();
 
         let p3 = vec!["line".to_string(), self.token.line.to_string()].join(" ").red();
 
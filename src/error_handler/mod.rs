@@ -2,18 +2,20 @@ mod error;
 
 pub use error::ViskumError;
 pub use error::AbortReason;
+pub use error::LoopControl;
 
 pub struct ErrorHandler {
     errors: Vec<ViskumError>,
+    file: String,
 }
 
 impl ErrorHandler {
-    pub fn new() -> Self {
-        ErrorHandler { errors: Vec::new() }
+    pub fn new(file: &str) -> Self {
+        ErrorHandler { errors: Vec::new(), file: file.to_string() }
     }
 
     pub fn report_error(&mut self, error: ViskumError) {
-        self.errors.push(error)
+        self.errors.push(error.in_file(&self.file))
     }
 
     pub fn print_errors(&self) {
@@ -1,19 +1,96 @@
-use crate::{ token::{ Literal, TokenType, Token }, error_handler::ViskumError };
+use std::rc::Rc;
 
-fn operation_error(op: &TokenType, left: &Literal, right: &Literal) -> ViskumError {
+use crate::{ token::{ Literal, Token, TokenType }, error_handler::ViskumError };
+
+fn operation_error(operator: &Token, left: &Literal, right: &Literal) -> ViskumError {
     ViskumError::new(
         format!(
             "'{}' is not defined for {} and {}",
-            op.to_lexeme(),
+            operator.ttype.to_lexeme(),
             left.to_type_string(),
             right.to_type_string()
         ).as_str(),
-        Token::new(op.clone(), op.to_lexeme(), None, 0),
-        "file.vs"
+        operator.clone()
     )
 }
 
+fn overflow_error(operator: &Token, left: &Literal, right: &Literal) -> ViskumError {
+    ViskumError::new(
+        format!(
+            "Integer overflow: The result of {} {} {} is too large for an int",
+            left,
+            operator.lexeme,
+            right
+        ).as_str(),
+        operator.clone()
+    )
+}
+
+/// Two numeric operands. An int is promoted to a float when it's mixed with a float
+enum Numbers {
+    Ints(i64, i64),
+    Floats(f64, f64),
+}
+
+fn numbers(left: &Literal, right: &Literal) -> Option<Numbers> {
+    match (left, right) {
+        (Literal::Int(x), Literal::Int(y)) => Some(Numbers::Ints(*x, *y)),
+        (Literal::Int(x), Literal::Num(y)) => Some(Numbers::Floats(*x as f64, *y)),
+        (Literal::Num(x), Literal::Int(y)) => Some(Numbers::Floats(*x, *y as f64)),
+        (Literal::Num(x), Literal::Num(y)) => Some(Numbers::Floats(*x, *y)),
+        _ => None,
+    }
+}
+
+fn checked_int(
+    result: Option<i64>,
+    operator: &Token,
+    left: &Literal,
+    right: &Literal
+) -> Result<Literal, ViskumError> {
+    match result {
+        Some(x) => Ok(Literal::Int(x)),
+        None => Err(overflow_error(operator, left, right)),
+    }
+}
+
+/// Applies a binary operator. Kept out of line so that the temporaries of
+/// every operation don't make the recursive evaluate frame bigger
+#[inline(never)]
+pub fn apply(left: &Literal, right: &Literal, operator: &Token) -> Output {
+    match operator.ttype {
+        TokenType::EqualEqual => Ok(Literal::Bool(is_equal(left, right))),
+        TokenType::BangEqual => Ok(Literal::Bool(!is_equal(left, right))),
+        TokenType::Greater => greater(left, right, operator),
+        TokenType::GreaterEqual => greater_equal(left, right, operator),
+        TokenType::Less => less(left, right, operator),
+        TokenType::LessEqual => less_equal(left, right, operator),
+        TokenType::Minus => minus(left, right, operator),
+        TokenType::Plus => plus(left, right, operator),
+        TokenType::Slash => division(left, right, operator),
+        TokenType::Star => multiplication(left, right, operator),
+        TokenType::Power => exponential(left, right, operator),
+        TokenType::Percent => modulo(left, right, operator),
+        TokenType::TildeSlash => int_division(left, right, operator),
+        TokenType::Ampersand => bitwise_and(left, right, operator),
+        TokenType::Pipe => bitwise_or(left, right, operator),
+        TokenType::ShiftLeft => shift_left(left, right, operator),
+        TokenType::ShiftRight => shift_right(left, right, operator),
+        TokenType::In => contains(left, right, operator),
+        _ => Ok(Literal::Null),
+    }
+}
+
 pub fn is_equal(left: &Literal, right: &Literal) -> bool {
+    equal(left, right, &mut Vec::new())
+}
+
+/// The pairs of lists and maps being compared. A list that contains itself is
+/// equal to another if they're equal everywhere else, so a pair that comes up
+/// again inside of itself counts as equal
+type Comparing = Vec<(*const (), *const ())>;
+
+fn equal(left: &Literal, right: &Literal, comparing: &mut Comparing) -> bool {
     match (left, right) {
         (Literal::Null, Literal::Null) => {
             return true;
@@ -21,137 +98,400 @@ pub fn is_equal(left: &Literal, right: &Literal) -> bool {
         (Literal::Str(str1), Literal::Str(str2)) => {
             return str1 == str2;
         }
-        (Literal::Num(x), Literal::Num(y)) => {
-            return x == y;
-        }
         (Literal::Bool(b1), Literal::Bool(b2)) => {
             return b1 == b2;
         }
-        _ => false,
+        (Literal::Class(c1), Literal::Class(c2)) => {
+            return c1 == c2;
+        }
+        (Literal::Instance(i1), Literal::Instance(i2)) => {
+            return Rc::ptr_eq(i1, i2);
+        }
+        (Literal::Exception(e1), Literal::Exception(e2)) => {
+            return Rc::ptr_eq(e1, e2);
+        }
+        (Literal::Module(m1), Literal::Module(m2)) => {
+            return Rc::ptr_eq(m1, m2);
+        }
+        (Literal::Iterator(i1), Literal::Iterator(i2)) => {
+            return Rc::ptr_eq(i1, i2);
+        }
+        (Literal::Range(r1), Literal::Range(r2)) => {
+            return r1 == r2;
+        }
+        (Literal::Enum(e1), Literal::Enum(e2)) => {
+            return e1 == e2;
+        }
+        (Literal::Variant(v1), Literal::Variant(v2)) => {
+            return v1.enum_type == v2.enum_type &&
+                v1.tag() == v2.tag() &&
+                v1.values.iter().zip(v2.values.iter()).all(|(x, y)| equal(x, y, comparing));
+        }
+        (Literal::List(l1), Literal::List(l2)) => {
+            return compare_once(Rc::as_ptr(l1).cast(), Rc::as_ptr(l2).cast(), comparing, |comparing| {
+                let (l1, l2) = (l1.borrow(), l2.borrow());
+                l1.len() == l2.len() && l1.iter().zip(l2.iter()).all(|(x, y)| equal(x, y, comparing))
+            });
+        }
+        (Literal::Tuple(t1), Literal::Tuple(t2)) => {
+            return t1.len() == t2.len() && t1.iter().zip(t2.iter()).all(|(x, y)| equal(x, y, comparing));
+        }
+        (Literal::Map(m1), Literal::Map(m2)) => {
+            return compare_once(Rc::as_ptr(m1).cast(), Rc::as_ptr(m2).cast(), comparing, |comparing| {
+                let (m1, m2) = (m1.borrow(), m2.borrow());
+                let invalid = Token::invalid(None);
+
+                m1.len() == m2.len() &&
+                    m1.entries().iter().all(|(key, v1)| {
+                        match m2.get(key, &invalid) {
+                            Ok(Some(v2)) => equal(v1, &v2, comparing),
+                            _ => false,
+                        }
+                    })
+            });
+        }
+        _ => {
+            match numbers(left, right) {
+                Some(Numbers::Ints(x, y)) => x == y,
+                Some(Numbers::Floats(x, y)) => x == y,
+                None => false,
+            }
+        }
     }
 }
 
+fn compare_once(
+    left: *const (),
+    right: *const (),
+    comparing: &mut Comparing,
+    compare: impl FnOnce(&mut Comparing) -> bool
+) -> bool {
+    if left == right || comparing.contains(&(left, right)) {
+        return true;
+    }
+
+    comparing.push((left, right));
+    let equal = compare(comparing);
+    comparing.pop();
+
+    equal
+}
+
 type Output = Result<Literal, ViskumError>;
 
-pub fn greater(left: &Literal, right: &Literal) -> Output {
-    match (left, right) {
-        (Literal::Num(x), Literal::Num(y)) => {
+pub fn greater(left: &Literal, right: &Literal, operator: &Token) -> Output {
+    match (numbers(left, right), left, right) {
+        (Some(Numbers::Ints(x, y)), _, _) => {
             return Ok(Literal::Bool(x > y));
         }
-        (Literal::Str(str1), Literal::Str(str2)) => {
+        (Some(Numbers::Floats(x, y)), _, _) => {
+            return Ok(Literal::Bool(x > y));
+        }
+        (None, Literal::Str(str1), Literal::Str(str2)) => {
             return Ok(Literal::Bool(str1 > str2));
         }
         _ => {
-            return Err(operation_error(&TokenType::Greater, left, right));
+            return Err(operation_error(operator, left, right));
         }
     }
 }
 
-pub fn greater_equal(left: &Literal, right: &Literal) -> Output {
-    match (left, right) {
-        (Literal::Num(x), Literal::Num(y)) => {
+pub fn greater_equal(left: &Literal, right: &Literal, operator: &Token) -> Output {
+    match (numbers(left, right), left, right) {
+        (Some(Numbers::Ints(x, y)), _, _) => {
             return Ok(Literal::Bool(x >= y));
         }
-        (Literal::Str(str1), Literal::Str(str2)) => {
+        (Some(Numbers::Floats(x, y)), _, _) => {
+            return Ok(Literal::Bool(x >= y));
+        }
+        (None, Literal::Str(str1), Literal::Str(str2)) => {
             return Ok(Literal::Bool(str1 >= str2));
         }
         _ => {
-            return Err(operation_error(&TokenType::GreaterEqual, left, right));
+            return Err(operation_error(operator, left, right));
         }
     }
 }
 
-pub fn less(left: &Literal, right: &Literal) -> Output {
-    match (left, right) {
-        (Literal::Num(x), Literal::Num(y)) => {
+pub fn less(left: &Literal, right: &Literal, operator: &Token) -> Output {
+    match (numbers(left, right), left, right) {
+        (Some(Numbers::Ints(x, y)), _, _) => {
             return Ok(Literal::Bool(x < y));
         }
-        (Literal::Str(str1), Literal::Str(str2)) => {
+        (Some(Numbers::Floats(x, y)), _, _) => {
+            return Ok(Literal::Bool(x < y));
+        }
+        (None, Literal::Str(str1), Literal::Str(str2)) => {
             return Ok(Literal::Bool(str1 < str2));
         }
         _ => {
-            return Err(operation_error(&TokenType::Less, left, right));
+            return Err(operation_error(operator, left, right));
         }
     }
 }
 
-pub fn less_equal(left: &Literal, right: &Literal) -> Output {
-    match (left, right) {
-        (Literal::Num(x), Literal::Num(y)) => {
+pub fn less_equal(left: &Literal, right: &Literal, operator: &Token) -> Output {
+    match (numbers(left, right), left, right) {
+        (Some(Numbers::Ints(x, y)), _, _) => {
             return Ok(Literal::Bool(x <= y));
         }
-        (Literal::Str(str1), Literal::Str(str2)) => {
+        (Some(Numbers::Floats(x, y)), _, _) => {
+            return Ok(Literal::Bool(x <= y));
+        }
+        (None, Literal::Str(str1), Literal::Str(str2)) => {
             return Ok(Literal::Bool(str1 <= str2));
         }
         _ => {
-            return Err(operation_error(&TokenType::LessEqual, left, right));
+            return Err(operation_error(operator, left, right));
         }
     }
 }
 
-pub fn plus(left: &Literal, right: &Literal) -> Output {
-    match (left, right) {
-        (Literal::Num(x), Literal::Num(y)) => {
+pub fn plus(left: &Literal, right: &Literal, operator: &Token) -> Output {
+    match (numbers(left, right), left, right) {
+        (Some(Numbers::Ints(x, y)), _, _) => {
+            return checked_int(x.checked_add(y), operator, left, right);
+        }
+        (Some(Numbers::Floats(x, y)), _, _) => {
             return Ok(Literal::Num(x + y));
         }
-        (Literal::Str(str1), Literal::Str(str2)) => {
-            return Ok(Literal::Str(format!("{}{}", str1, str2).to_string()));
+        (None, Literal::Str(str1), Literal::Str(str2)) => {
+            return Ok(Literal::Str(format!("{}{}", str1, str2)));
         }
-        (Literal::Num(x), Literal::Str(str)) => {
-            return Ok(Literal::Str(format!("{}{}", str, x.to_string()).to_string()));
+        (None, Literal::Int(_) | Literal::Num(_), Literal::Str(_)) => {
+            return Ok(Literal::Str(format!("{}{}", left, right)));
         }
-        (Literal::Str(str), Literal::Num(x)) => {
-            return Ok(Literal::Str(format!("{}{}", str, x.to_string()).to_string()));
+        (None, Literal::Str(_), Literal::Int(_) | Literal::Num(_)) => {
+            return Ok(Literal::Str(format!("{}{}", left, right)));
         }
         _ => {
-            return Err(operation_error(&TokenType::Plus, left, right));
+            return Err(operation_error(operator, left, right));
         }
     }
 }
 
-pub fn minus(left: &Literal, right: &Literal) -> Output {
-    match (left, right) {
-        (Literal::Num(x), Literal::Num(y)) => {
+pub fn minus(left: &Literal, right: &Literal, operator: &Token) -> Output {
+    match (numbers(left, right), left, right) {
+        (Some(Numbers::Ints(x, y)), _, _) => {
+            return checked_int(x.checked_sub(y), operator, left, right);
+        }
+        (Some(Numbers::Floats(x, y)), _, _) => {
             return Ok(Literal::Num(x - y));
         }
-        (Literal::Str(str1), Literal::Str(str2)) => {
+        (None, Literal::Str(str1), Literal::Str(str2)) => {
             return Ok(Literal::Str(str1.replace(str2, "")));
         }
         _ => {
-            return Err(operation_error(&TokenType::Minus, left, right));
+            return Err(operation_error(operator, left, right));
         }
     }
 }
 
-pub fn division(left: &Literal, right: &Literal) -> Output {
-    match (left, right) {
-        (Literal::Num(x), Literal::Num(y)) => {
+/// Division always results in a float, even when both operands are ints
+pub fn division(left: &Literal, right: &Literal, operator: &Token) -> Output {
+    match numbers(left, right) {
+        Some(Numbers::Ints(x, y)) => {
+            return Ok(Literal::Num((x as f64) / (y as f64)));
+        }
+        Some(Numbers::Floats(x, y)) => {
             return Ok(Literal::Num(x / y));
         }
+        None => {
+            return Err(operation_error(operator, left, right));
+        }
+    }
+}
+
+pub fn multiplication(left: &Literal, right: &Literal, operator: &Token) -> Output {
+    match numbers(left, right) {
+        Some(Numbers::Ints(x, y)) => {
+            return checked_int(x.checked_mul(y), operator, left, right);
+        }
+        Some(Numbers::Floats(x, y)) => {
+            return Ok(Literal::Num(x * y));
+        }
+        None => {
+            return Err(operation_error(operator, left, right));
+        }
+    }
+}
+
+/// An int raised to a non-negative int is an int. Everything else is a float
+pub fn exponential(left: &Literal, right: &Literal, operator: &Token) -> Output {
+    match numbers(left, right) {
+        Some(Numbers::Ints(x, y)) if y >= 0 => {
+            let result = u32::try_from(y)
+                .ok()
+                .and_then(|y| x.checked_pow(y));
+
+            return checked_int(result, operator, left, right);
+        }
+        Some(Numbers::Ints(x, y)) => {
+            return Ok(Literal::Num((x as f64).powf(y as f64)));
+        }
+        Some(Numbers::Floats(x, y)) => {
+            return Ok(Literal::Num(x.powf(y)));
+        }
+        None => {
+            return Err(operation_error(operator, left, right));
+        }
+    }
+}
+
+fn division_by_zero_error(operator: &Token) -> ViskumError {
+    ViskumError::new(
+        format!("Division by zero with '{}'", operator.ttype.to_lexeme()).as_str(),
+        operator.clone()
+    )
+}
+
+/// The remainder has the same sign as the right operand, so -7 % 3 is 2
+pub fn modulo(left: &Literal, right: &Literal, operator: &Token) -> Output {
+    match numbers(left, right) {
+        Some(Numbers::Ints(_, 0)) => {
+            return Err(division_by_zero_error(operator));
+        }
+        Some(Numbers::Ints(x, y)) => {
+            let remainder = x.wrapping_rem(y);
+
+            if remainder != 0 && (remainder < 0) != (y < 0) {
+                return Ok(Literal::Int(remainder + y));
+            }
+            return Ok(Literal::Int(remainder));
+        }
+        Some(Numbers::Floats(_, 0.0)) => {
+            return Err(division_by_zero_error(operator));
+        }
+        Some(Numbers::Floats(x, y)) => {
+            return Ok(Literal::Num(x - y * (x / y).floor()));
+        }
+        None => {
+            return Err(operation_error(operator, left, right));
+        }
+    }
+}
+
+/// Divides and rounds down to the nearest int, so -7 ~/ 2 is -4
+pub fn int_division(left: &Literal, right: &Literal, operator: &Token) -> Output {
+    match numbers(left, right) {
+        Some(Numbers::Ints(_, 0)) => {
+            return Err(division_by_zero_error(operator));
+        }
+        Some(Numbers::Ints(x, y)) => {
+            let quotient = x
+                .checked_div(y)
+                .map(|quotient| {
+                    if x % y != 0 && (x < 0) != (y < 0) { quotient - 1 } else { quotient }
+                });
+
+            return checked_int(quotient, operator, left, right);
+        }
+        Some(Numbers::Floats(_, 0.0)) => {
+            return Err(division_by_zero_error(operator));
+        }
+        Some(Numbers::Floats(x, y)) => {
+            let quotient = (x / y).floor();
+
+            if quotient.is_nan() || quotient < (i64::MIN as f64) || quotient >= (i64::MAX as f64) {
+                return Err(overflow_error(operator, left, right));
+            }
+            return Ok(Literal::Int(quotient as i64));
+        }
+        None => {
+            return Err(operation_error(operator, left, right));
+        }
+    }
+}
+
+pub fn bitwise_and(left: &Literal, right: &Literal, operator: &Token) -> Output {
+    match (left, right) {
+        (Literal::Int(x), Literal::Int(y)) => {
+            return Ok(Literal::Int(x & y));
+        }
         _ => {
-            return Err(operation_error(&TokenType::Slash, left, right));
+            return Err(operation_error(operator, left, right));
         }
     }
 }
 
-pub fn multiplication(left: &Literal, right: &Literal) -> Output {
+pub fn bitwise_or(left: &Literal, right: &Literal, operator: &Token) -> Output {
     match (left, right) {
-        (Literal::Num(x), Literal::Num(y)) => {
-            return Ok(Literal::Num(x * y));
+        (Literal::Int(x), Literal::Int(y)) => {
+            return Ok(Literal::Int(x | y));
         }
         _ => {
-            return Err(operation_error(&TokenType::Star, left, right));
+            return Err(operation_error(operator, left, right));
         }
     }
 }
 
-pub fn exponential(left: &Literal, right: &Literal) -> Output {
+fn shift_amount(amount: i64, operator: &Token) -> Result<u32, ViskumError> {
+    match u32::try_from(amount) {
+        Ok(amount) if amount < 64 => Ok(amount),
+        _ =>
+            Err(
+                ViskumError::new(
+                    format!(
+                        "Cannot shift by {} with '{}': The amount must be between 0 and 63",
+                        amount,
+                        operator.ttype.to_lexeme()
+                    ).as_str(),
+                    operator.clone()
+                )
+            ),
+    }
+}
+
+pub fn shift_left(left: &Literal, right: &Literal, operator: &Token) -> Output {
     match (left, right) {
-        (Literal::Num(x), Literal::Num(y)) => {
-            return Ok(Literal::Num(x.powf(*y)));
+        (Literal::Int(x), Literal::Int(y)) => {
+            return Ok(Literal::Int(x << shift_amount(*y, operator)?));
+        }
+        _ => {
+            return Err(operation_error(operator, left, right));
+        }
+    }
+}
+
+/// Shifts keep the sign, so -8 >> 1 is -4
+pub fn shift_right(left: &Literal, right: &Literal, operator: &Token) -> Output {
+    match (left, right) {
+        (Literal::Int(x), Literal::Int(y)) => {
+            return Ok(Literal::Int(x >> shift_amount(*y, operator)?));
+        }
+        _ => {
+            return Err(operation_error(operator, left, right));
+        }
+    }
+}
+
+/// x in collection. Lists and tuples contain their elements, maps their keys,
+/// strings their substrings and ranges their ints
+pub fn contains(left: &Literal, right: &Literal, operator: &Token) -> Output {
+    match (left, right) {
+        (_, Literal::List(list)) => {
+            return Ok(Literal::Bool(list.borrow().iter().any(|element| is_equal(left, element))));
+        }
+        (_, Literal::Tuple(tuple)) => {
+            return Ok(Literal::Bool(tuple.iter().any(|element| is_equal(left, element))));
+        }
+        (_, Literal::Map(map)) => {
+            return Ok(Literal::Bool(map.borrow().get(left, operator)?.is_some()));
+        }
+        (Literal::Str(substring), Literal::Str(str)) => {
+            return Ok(Literal::Bool(str.contains(substring.as_str())));
+        }
+        (Literal::Int(x), Literal::Range(range)) => {
+            return Ok(Literal::Bool(range.contains(*x)));
+        }
+        (Literal::Num(x), Literal::Range(range)) => {
+            return Ok(Literal::Bool(x.fract() == 0.0 && range.contains(*x as i64)));
+        }
+        (_, Literal::Range(_)) => {
+            return Ok(Literal::Bool(false));
         }
         _ => {
-            return Err(operation_error(&TokenType::Power, left, right));
+            return Err(operation_error(operator, left, right));
         }
     }
 }
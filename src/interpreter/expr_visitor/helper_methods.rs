@@ -0,0 +1,187 @@
+use crate::{
+    expr::{ Expr, CallExpr },
+    token::{ Literal, Token, TokenType },
+    error_handler::ViskumError,
+    interpreter::Interpreter,
+    viskum_callable::{ Callable, Arity },
+    viskum_function::NamedArgument,
+};
+
+use super::binary_operations;
+
+type BinaryOperation = fn(&Literal, &Literal, &Token) -> Result<Literal, ViskumError>;
+
+/// A callable with its positional and named arguments
+pub type CallTarget = (Callable, Vec<Literal>, Vec<(Token, Literal)>);
+
+impl<'a> Interpreter<'a> {
+    /// The value an assignment like '=', '+=' or '++' stores. Kept out of line
+    /// like the binary operators, so the recursive evaluate frame stays small
+    #[inline(never)]
+    pub(super) fn assignment_value(
+        &self,
+        assignment_token: &Token,
+        current: Literal,
+        value: &Expr
+    ) -> Result<Literal, ViskumError> {
+        let operation: BinaryOperation = match assignment_token.ttype {
+            TokenType::Equal => {
+                return self.evaluate(value);
+            }
+            TokenType::PlusEqual => binary_operations::plus,
+            TokenType::MinusEqual => binary_operations::minus,
+            TokenType::StarEqual => binary_operations::multiplication,
+            TokenType::SlashEqual => binary_operations::division,
+            TokenType::PowerEqual => binary_operations::exponential,
+            TokenType::PercentEqual => binary_operations::modulo,
+            TokenType::TildeSlashEqual => binary_operations::int_division,
+            TokenType::AmpersandEqual => binary_operations::bitwise_and,
+            TokenType::PipeEqual => binary_operations::bitwise_or,
+            TokenType::ShiftLeftEqual => binary_operations::shift_left,
+            TokenType::ShiftRightEqual => binary_operations::shift_right,
+            _ => {
+                return self.step_value(assignment_token, current);
+            }
+        };
+
+        let right = self.evaluate(value)?;
+
+        operation(&current, &right, assignment_token)
+    }
+
+    /// The new value for '++' and '--'
+    fn step_value(&self, assignment_token: &Token, current: Literal) -> Result<Literal, ViskumError> {
+        match assignment_token.ttype {
+            TokenType::Increment | TokenType::Decrement => {
+                let adjustment = match assignment_token.ttype {
+                    TokenType::Increment => 1,
+                    _ => -1,
+                };
+
+                match current {
+                    Literal::Num(x) => Ok(Literal::Num(x + (adjustment as f64))),
+                    Literal::Int(x) =>
+                        match x.checked_add(adjustment) {
+                            Some(x) => Ok(Literal::Int(x)),
+                            None =>
+                                Err(
+                                    ViskumError::new(
+                                        format!(
+                                            "Integer overflow: Cannot apply '{}' to {}",
+                                            assignment_token.lexeme,
+                                            x
+                                        ).as_str(),
+                                        assignment_token.clone()
+                                    )
+                                ),
+                        }
+                    _ =>
+                        Err(
+                            ViskumError::new(
+                                format!(
+                                    "{} is not defined for {}",
+                                    assignment_token.lexeme,
+                                    current.to_type_string()
+                                ).as_str(),
+                                assignment_token.clone()
+                            )
+                        ),
+                }
+            }
+            _ =>
+                Err(
+                    ViskumError::new(
+                        format!("Invalid assignment: {}", assignment_token.lexeme).as_str(),
+                        assignment_token.clone()
+                    )
+                ),
+        }
+    }
+
+    /// Evaluates the callee and arguments of a call without performing it
+    pub fn call_target(&self, expr: &CallExpr) -> Result<CallTarget, ViskumError> {
+        let callee = self.evaluate(&expr.callee)?;
+
+        let arguments = self.evaluate_arguments(&expr.arguments)?;
+        let named = self.evaluate_named_arguments(&expr.named)?;
+
+        match callee {
+            Literal::Func(func) => {
+                self.check_arity(func.arity, arguments.len() + named.len(), &expr.paren)?;
+
+                Ok((func, arguments, named))
+            }
+            Literal::Class(class) =>
+                Err(
+                    ViskumError::new(
+                        format!(
+                            "Class '{}' must be instantiated with 'new'",
+                            class.name
+                        ).as_str(),
+                        expr.paren.clone()
+                    )
+                ),
+            _ =>
+                Err(
+                    ViskumError::new(
+                        format!("A {} is not callable", callee.to_type_string()).as_str(),
+                        expr.paren.clone()
+                    )
+                ),
+        }
+    }
+
+    pub(super) fn evaluate_arguments(&self, arguments: &[Expr]) -> Result<Vec<Literal>, ViskumError> {
+        arguments
+            .iter()
+            .map(|argument| self.evaluate(argument))
+            .collect()
+    }
+
+    pub(super) fn evaluate_named_arguments(
+        &self,
+        named: &[NamedArgument]
+    ) -> Result<Vec<(Token, Literal)>, ViskumError> {
+        named
+            .iter()
+            .map(|argument| Ok((argument.token.clone(), self.evaluate(&argument.value)?)))
+            .collect()
+    }
+
+    pub(super) fn check_arity(
+        &self,
+        arity: Arity,
+        count: usize,
+        paren: &Token
+    ) -> Result<(), ViskumError> {
+        if !arity.accepts(count) {
+            let noun = if arity.max.unwrap_or(arity.min) == 1 { "argument" } else { "arguments" };
+
+            return Err(
+                ViskumError::new(
+                    format!("Expected {} {} but received {}", arity, noun, count).as_str(),
+                    paren.clone()
+                )
+            );
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn range_bound(&self, expr: &Expr, operator: &Token) -> Result<i64, ViskumError> {
+        match self.evaluate(expr)? {
+            Literal::Int(x) => Ok(x),
+            value =>
+                Err(
+                    ViskumError::new(
+                        format!(
+                            "Ranges can only be made of ints, but got {}",
+                            value.to_type_string()
+                        ).as_str(),
+                        operator.clone()
+                    )
+                ),
+        }
+    }
+}
+
@@ -0,0 +1,105 @@
+use crate::{ token::{ Literal, Token }, error_handler::ViskumError };
+
+type Output = Result<Literal, ViskumError>;
+
+fn to_index(index: &Literal, length: usize, bracket: &Token) -> Result<usize, ViskumError> {
+    match index {
+        Literal::Int(x) => {
+            if *x < 0 {
+                Err(
+                    ViskumError::new(
+                        format!("Index {} is negative: Indices start at 0", x).as_str(),
+                        bracket.clone()
+                    )
+                )
+            } else if (*x as usize) >= length {
+                Err(
+                    ViskumError::new(
+                        format!("Index {} is out of bounds for length {}", x, length).as_str(),
+                        bracket.clone()
+                    )
+                )
+            } else {
+                Ok(*x as usize)
+            }
+        }
+        _ =>
+            Err(
+                ViskumError::new(
+                    format!(
+                        "Cannot index with {}: Indices must be ints",
+                        index.to_repr_string()
+                    ).as_str(),
+                    bracket.clone()
+                )
+            ),
+    }
+}
+
+pub fn get_index(object: &Literal, index: &Literal, bracket: &Token) -> Output {
+    match object {
+        Literal::List(list) => {
+            let list = list.borrow();
+            let i = to_index(index, list.len(), bracket)?;
+
+            Ok(list[i].clone())
+        }
+        Literal::Tuple(tuple) => {
+            let i = to_index(index, tuple.len(), bracket)?;
+
+            Ok(tuple[i].clone())
+        }
+        Literal::Str(str) => {
+            let i = to_index(index, str.chars().count(), bracket)?;
+
+            Ok(Literal::Str(str.chars().nth(i).unwrap_or_default().to_string()))
+        }
+        Literal::Map(map) => {
+            match map.borrow().get(index, bracket)? {
+                Some(value) => Ok(value),
+                None =>
+                    Err(
+                        ViskumError::new(
+                            format!("Key {} is not in the map", index.to_repr_string()).as_str(),
+                            bracket.clone()
+                        )
+                    ),
+            }
+        }
+        _ =>
+            Err(
+                ViskumError::new(
+                    format!("A {} cannot be indexed", object.to_type_string()).as_str(),
+                    bracket.clone()
+                )
+            ),
+    }
+}
+
+pub fn set_index(object: &Literal, index: &Literal, value: Literal, bracket: &Token) -> Output {
+    match object {
+        Literal::List(list) => {
+            let mut list = list.borrow_mut();
+            let i = to_index(index, list.len(), bracket)?;
+
+            list[i] = value.clone();
+
+            Ok(value)
+        }
+        Literal::Map(map) => {
+            map.borrow_mut().insert(index.clone(), value.clone(), bracket)?;
+
+            Ok(value)
+        }
+        _ =>
+            Err(
+                ViskumError::new(
+                    format!(
+                        "Cannot assign to an index of a {}",
+                        object.to_type_string()
+                    ).as_str(),
+                    bracket.clone()
+                )
+            ),
+    }
+}
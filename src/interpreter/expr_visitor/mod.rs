@@ -1,12 +1,22 @@
+use std::{ rc::Rc, cell::RefCell };
+
 mod binary_operations;
-use crate::viskum_callable::ViskumCallable;
+mod index_operations;
+mod pattern_matching;
+mod helper_methods;
+use crate::viskum_callable::{ ViskumCallable, Callable };
 
 use crate::{
     expr::*,
     token::{ Literal, TokenType },
-    error_handler::ViskumError,
-    util::factorial,
-    environment::environment_value::EnvironmentValue,
+    error_handler::{ ViskumError, LoopControl },
+    util::{ factorial, int_factorial },
+    viskum_instance::ViskumInstance,
+    viskum_map::ViskumMap,
+    viskum_function::ViskumFunction,
+    viskum_range::ViskumRange,
+    viskum_iterator::ViskumIterator,
+    viskum_partial::ViskumPartial,
 };
 
 use super::Interpreter;
@@ -18,24 +28,7 @@ impl<'a> ExprVisitor<Output> for Interpreter<'a> {
         let left = self.evaluate(&expr.left)?;
         let right = self.evaluate(&expr.right)?;
 
-        match expr.operator.ttype {
-            TokenType::EqualEqual => {
-                Ok(Literal::Bool(binary_operations::is_equal(&left, &right)))
-            }
-            TokenType::BangEqual => {
-                Ok(Literal::Bool(!binary_operations::is_equal(&left, &right)))
-            }
-            TokenType::Greater => { Ok(binary_operations::greater(&left, &right)?) }
-            TokenType::GreaterEqual => { Ok(binary_operations::greater_equal(&left, &right)?) }
-            TokenType::Less => { Ok(binary_operations::less(&left, &right)?) }
-            TokenType::LessEqual => { Ok(binary_operations::less_equal(&left, &right)?) }
-            TokenType::Minus => { Ok(binary_operations::minus(&left, &right)?) }
-            TokenType::Plus => { Ok(binary_operations::plus(&left, &right)?) }
-            TokenType::Slash => { Ok(binary_operations::division(&left, &right)?) }
-            TokenType::Star => { Ok(binary_operations::multiplication(&left, &right)?) }
-            TokenType::Power => { Ok(binary_operations::exponential(&left, &right)?) }
-            _ => { Ok(Literal::Null) }
-        }
+        binary_operations::apply(&left, &right, &expr.operator)
     }
 
     fn visit_grouping_expr(&self, expr: &GroupingExpr) -> Result<Output, ViskumError> {
@@ -53,6 +46,18 @@ impl<'a> ExprVisitor<Output> for Interpreter<'a> {
             TokenType::Minus =>
                 match right {
                     Literal::Num(x) => { Ok(Literal::Num(-x)) }
+                    Literal::Int(x) => {
+                        match x.checked_neg() {
+                            Some(x) => Ok(Literal::Int(x)),
+                            None =>
+                                Err(
+                                    ViskumError::new(
+                                        format!("Integer overflow: -({}) is too large for an int", x).as_str(),
+                                        expr.operator.clone()
+                                    )
+                                ),
+                        }
+                    }
                     _ => {
                         Err(
                             ViskumError::new(
@@ -61,20 +66,33 @@ impl<'a> ExprVisitor<Output> for Interpreter<'a> {
                                     expr.operator.lexeme,
                                     right.to_type_string()
                                 ).as_str(),
-                                expr.operator.clone(),
-                                "file.vs"
+                                expr.operator.clone()
                             )
                         )
                     }
                 }
             TokenType::Bang => Ok(Literal::Bool(!self.is_truthy(&right))),
+            TokenType::Tilde =>
+                match right {
+                    Literal::Int(x) => Ok(Literal::Int(!x)),
+                    _ =>
+                        Err(
+                            ViskumError::new(
+                                format!(
+                                    "'{}' is not defined for {}",
+                                    expr.operator.lexeme,
+                                    right.to_type_string()
+                                ).as_str(),
+                                expr.operator.clone()
+                            )
+                        ),
+                }
 
             _ =>
                 Err(
                     ViskumError::new(
                         format!("Invalid prefix: {}", expr.operator.lexeme).as_str(),
-                        expr.operator.clone(),
-                        "file.vs"
+                        expr.operator.clone()
                     )
                 ),
         }
@@ -89,6 +107,29 @@ impl<'a> ExprVisitor<Output> for Interpreter<'a> {
                     Literal::Num(x) => {
                         return Ok(Literal::Num(factorial(x)));
                     }
+                    Literal::Int(x) if x < 0 => {
+                        return Err(
+                            ViskumError::new(
+                                format!("Factorial is not defined for negative ints like {}", x).as_str(),
+                                expr.operator.clone()
+                            )
+                        );
+                    }
+                    Literal::Int(x) => {
+                        match int_factorial(x) {
+                            Some(x) => {
+                                return Ok(Literal::Int(x));
+                            }
+                            None => {
+                                return Err(
+                                    ViskumError::new(
+                                        format!("Integer overflow: {}! is too large for an int", x).as_str(),
+                                        expr.operator.clone()
+                                    )
+                                );
+                            }
+                        }
+                    }
                     lit => {
                         return Err(
                             ViskumError::new(
@@ -97,8 +138,7 @@ impl<'a> ExprVisitor<Output> for Interpreter<'a> {
                                     expr.operator.lexeme,
                                     lit.to_type_string()
                                 ).as_str(),
-                                expr.operator.clone(),
-                                "file.vs"
+                                expr.operator.clone()
                             )
                         );
                     }
@@ -108,8 +148,7 @@ impl<'a> ExprVisitor<Output> for Interpreter<'a> {
                 return Err(
                     ViskumError::new(
                         format!("Invalid postfix: {}", expr.operator.lexeme).as_str(),
-                        expr.operator.clone(),
-                        "file.vs"
+                        expr.operator.clone()
                     )
                 );
             }
@@ -129,91 +168,16 @@ impl<'a> ExprVisitor<Output> for Interpreter<'a> {
     }
 
     fn visit_variable_expr(&self, expr: &VariableExpr) -> Result<Output, ViskumError> {
-        Ok(self.environment_get(&expr.token)?)
+        self.environment_get(&expr.token, expr.depth.get())
     }
 
     fn visit_assign_expr(&self, expr: &AssignExpr) -> Result<Output, ViskumError> {
-        match expr.assignment_token.ttype {
-            | TokenType::Equal
-            | TokenType::PlusEqual
-            | TokenType::MinusEqual
-            | TokenType::StarEqual
-            | TokenType::SlashEqual
-            | TokenType::PowerEqual => {
-                let left = self.environment_get(&expr.token)?;
-
-                let right = self.evaluate(&expr.value)?;
-
-                let new_value = match expr.assignment_token.ttype {
-                    TokenType::Equal => right,
-                    TokenType::PlusEqual => binary_operations::plus(&left, &right)?,
-                    TokenType::MinusEqual => binary_operations::minus(&left, &right)?,
-                    TokenType::StarEqual => binary_operations::multiplication(&left, &right)?,
-                    TokenType::SlashEqual => binary_operations::division(&left, &right)?,
-                    TokenType::PowerEqual => binary_operations::exponential(&left, &right)?,
-                    _ => {
-                        return Err(
-                            ViskumError::new(
-                                format!(
-                                    "{} is not defined for {}",
-                                    expr.assignment_token.lexeme,
-                                    left.to_type_string()
-                                ).as_str(),
-                                expr.assignment_token.clone(),
-                                "file.vs"
-                            )
-                        );
-                    }
-                };
+        let current = self.environment_get(&expr.token, expr.depth.get())?;
+        let new_value = self.assignment_value(&expr.assignment_token, current.clone(), &expr.value)?;
 
-                return Ok(
-                    self.environment_assign(&expr.token, EnvironmentValue::new(new_value, false))?
-                );
-            }
-            TokenType::Increment | TokenType::Decrement => {
-                let adjustment = match expr.assignment_token.ttype {
-                    TokenType::Increment => 1.0,
-                    TokenType::Decrement => -1.0,
-                    _ => 0.0,
-                };
-
-                let variable_value = self.environment_get(&expr.token)?;
-                let variable_number = variable_value.to_num();
-
-                match variable_number {
-                    Ok(x) => {
-                        return Ok(
-                            self.environment_assign(
-                                &expr.token,
-                                EnvironmentValue::new(Literal::Num(x + adjustment), false)
-                            )?
-                        );
-                    }
-                    Err(_) => {
-                        return Err(
-                            ViskumError::new(
-                                format!(
-                                    "{} is not defined for {}",
-                                    expr.assignment_token.lexeme,
-                                    variable_value.to_type_string()
-                                ).as_str(),
-                                expr.assignment_token.clone(),
-                                "file.vs"
-                            )
-                        );
-                    }
-                }
-            }
-            _ => {
-                return Err(
-                    ViskumError::new(
-                        format!("Invalid assignment: {}", expr.assignment_token.lexeme).as_str(),
-                        expr.assignment_token.clone(),
-                        "file.vs"
-                    )
-                );
-            }
-        }
+        let new_value = self.environment_assign(&expr.token, expr.depth.get(), new_value)?;
+
+        Ok(if expr.postfix { current } else { new_value })
     }
 
     fn visit_logical_expr(&self, expr: &LogicalExpr) -> Result<Output, ViskumError> {
@@ -238,8 +202,7 @@ impl<'a> ExprVisitor<Output> for Interpreter<'a> {
                 return Err(
                     ViskumError::new(
                         format!("Invalid logical operator: {}", expr.operator.lexeme).as_str(),
-                        expr.operator.clone(),
-                        "file.vs"
+                        expr.operator.clone()
                     )
                 );
             }
@@ -247,38 +210,272 @@ impl<'a> ExprVisitor<Output> for Interpreter<'a> {
     }
 
     fn visit_call_expr(&self, expr: &CallExpr) -> Result<Output, ViskumError> {
-        let callee = self.evaluate(&expr.callee)?;
+        let (func, arguments, named) = self.call_target(expr)?;
+
+        func.call_named(self, &arguments, &named).map_err(|e| e.with_call_site(&expr.paren))
+    }
+
+    fn visit_partial_expr(&self, expr: &PartialExpr) -> Result<Output, ViskumError> {
+        let callable = match self.evaluate(&expr.callee)? {
+            Literal::Func(callable) => callable,
+            callee => {
+                return Err(
+                    ViskumError::new(
+                        format!("A {} is not callable", callee.to_type_string()).as_str(),
+                        expr.paren.clone()
+                    )
+                );
+            }
+        };
 
-        let mut arguments = Vec::new();
+        let arguments = expr.arguments
+            .iter()
+            .map(|argument| {
+                match argument {
+                    Expr::Placeholder(_) => Ok(None),
+                    argument => Ok(Some(self.evaluate(argument)?)),
+                }
+            })
+            .collect::<Result<Vec<Option<Literal>>, ViskumError>>()?;
+        let named = self.evaluate_named_arguments(&expr.named)?;
 
-        for argument in &expr.arguments {
-            arguments.push(self.evaluate(argument)?);
+        // The missing arguments come when the partial function is called, so
+        // only too many arguments is an error here
+        let count = arguments.len() + named.len();
+        if callable.arity.max.is_some_and(|max| count > max) {
+            self.check_arity(callable.arity, count, &expr.paren)?;
         }
 
-        if let Literal::Func(func) = callee {
-            if arguments.len() != func.arity {
-                return Err(
+        let partial = ViskumPartial::new(callable, arguments, named);
+        let arity = partial.arity();
+
+        Ok(Literal::Func(Callable { func: Rc::new(partial), arity }))
+    }
+
+    fn visit_placeholder_expr(&self, expr: &PlaceholderExpr) -> Result<Output, ViskumError> {
+        Err(
+            ViskumError::new(
+                "Cannot use '_' outside of the arguments of a call",
+                expr.token.clone()
+            )
+        )
+    }
+
+    fn visit_get_expr(&self, expr: &GetExpr) -> Result<Output, ViskumError> {
+        match self.evaluate(&expr.object)? {
+            Literal::Instance(instance) => ViskumInstance::get(&instance, &expr.token),
+            Literal::Enum(enum_type) => enum_type.get(&expr.token),
+            Literal::Variant(variant) => variant.get(&expr.token),
+            Literal::Module(module) => module.get(&expr.token),
+            Literal::Exception(exception) => exception.get(&expr.token),
+            Literal::Iterator(iterator) => ViskumIterator::get(&iterator, &expr.token),
+            object =>
+                Err(
                     ViskumError::new(
                         format!(
-                            "Expected {} arguments but received {}",
-                            func.arity,
-                            arguments.len()
+                            "Cannot read property '{}' of a {}",
+                            expr.token.lexeme,
+                            object.to_type_string()
                         ).as_str(),
-                        expr.paren.clone(),
-                        "file.vs"
+                        expr.token.clone()
                     )
-                );
+                ),
+        }
+    }
+
+    fn visit_set_expr(&self, expr: &SetExpr) -> Result<Output, ViskumError> {
+        match self.evaluate(&expr.object)? {
+            Literal::Instance(instance) => {
+                if expr.assignment_token.is(TokenType::Equal) {
+                    let new_value = self.evaluate(&expr.value)?;
+                    instance.borrow_mut().set(&expr.token, new_value.clone());
+
+                    return Ok(new_value);
+                }
+
+                let current = ViskumInstance::get(&instance, &expr.token)?;
+                let new_value = self.assignment_value(
+                    &expr.assignment_token,
+                    current.clone(),
+                    &expr.value
+                )?;
+
+                instance.borrow_mut().set(&expr.token, new_value.clone());
+
+                Ok(if expr.postfix { current } else { new_value })
+            }
+            object =>
+                Err(
+                    ViskumError::new(
+                        format!(
+                            "Cannot set property '{}' on a {}",
+                            expr.token.lexeme,
+                            object.to_type_string()
+                        ).as_str(),
+                        expr.token.clone()
+                    )
+                ),
+        }
+    }
+
+    fn visit_this_expr(&self, expr: &ThisExpr) -> Result<Output, ViskumError> {
+        self.environment_get(&expr.keyword, expr.depth.get())
+    }
+
+    fn visit_new_expr(&self, expr: &NewExpr) -> Result<Output, ViskumError> {
+        let class = self.evaluate(&expr.class)?;
+
+        let arguments = self.evaluate_arguments(&expr.arguments)?;
+        let named = self.evaluate_named_arguments(&expr.named)?;
+
+        match class {
+            Literal::Class(class) => {
+                self.check_arity(class.arity(), arguments.len() + named.len(), &expr.paren)?;
+
+                class.call_named(self, &arguments, &named).map_err(|e| e.with_call_site(&expr.paren))
+            }
+            _ =>
+                Err(
+                    ViskumError::new(
+                        format!(
+                            "A {} cannot be instantiated with 'new'",
+                            class.to_type_string()
+                        ).as_str(),
+                        expr.keyword.clone()
+                    )
+                ),
+        }
+    }
+
+    fn visit_list_expr(&self, expr: &ListExpr) -> Result<Output, ViskumError> {
+        Ok(Literal::new_list(self.evaluate_arguments(&expr.elements)?))
+    }
+
+    fn visit_tuple_expr(&self, expr: &TupleExpr) -> Result<Output, ViskumError> {
+        Ok(Literal::new_tuple(self.evaluate_arguments(&expr.elements)?))
+    }
+
+    fn visit_map_expr(&self, expr: &MapExpr) -> Result<Output, ViskumError> {
+        let mut map = ViskumMap::new();
+
+        for (key, value) in expr.keys.iter().zip(expr.values.iter()) {
+            map.insert(self.evaluate(key)?, self.evaluate(value)?, &expr.bracket)?;
+        }
+
+        Ok(Literal::Map(Rc::new(RefCell::new(map))))
+    }
+
+    fn visit_index_expr(&self, expr: &IndexExpr) -> Result<Output, ViskumError> {
+        let object = self.evaluate(&expr.object)?;
+        let index = self.evaluate(&expr.index)?;
+
+        index_operations::get_index(&object, &index, &expr.bracket)
+    }
+
+    fn visit_indexset_expr(&self, expr: &IndexSetExpr) -> Result<Output, ViskumError> {
+        let object = self.evaluate(&expr.object)?;
+        let index = self.evaluate(&expr.index)?;
+
+        if expr.assignment_token.is(TokenType::Equal) {
+            let new_value = self.evaluate(&expr.value)?;
+            return index_operations::set_index(&object, &index, new_value, &expr.bracket);
+        }
+
+        let current = index_operations::get_index(&object, &index, &expr.bracket)?;
+        let new_value = self.assignment_value(&expr.assignment_token, current.clone(), &expr.value)?;
+
+        let new_value = index_operations::set_index(&object, &index, new_value, &expr.bracket)?;
+
+        Ok(if expr.postfix { current } else { new_value })
+    }
+
+    fn visit_lambda_expr(&self, expr: &LambdaExpr) -> Result<Output, ViskumError> {
+        let function = ViskumFunction::new(
+            expr.declaration.clone(),
+            self.environment.borrow().clone()
+        );
+        let arity = function.arity();
+
+        Ok(Literal::Func(Callable { func: Rc::new(function), arity }))
+    }
+
+    fn visit_interpolation_expr(&self, expr: &InterpolationExpr) -> Result<Output, ViskumError> {
+        let mut result = expr.parts[0].clone();
+
+        for (expression, part) in expr.expressions.iter().zip(&expr.parts[1..]) {
+            result.push_str(&self.evaluate(expression)?.to_string());
+            result.push_str(part);
+        }
+
+        Ok(Literal::Str(result))
+    }
+
+    fn visit_block_expr(&self, expr: &BlockExpr) -> Result<Output, ViskumError> {
+        self.with_environment(self.new_child_environment(), || {
+            for statement in &expr.statements {
+                self.execute(statement)?;
+            }
+
+            match &expr.value {
+                Some(value) => self.evaluate(value),
+                None => Ok(Literal::Null),
             }
+        })
+    }
 
-            return Ok(func.call(self, &arguments)?);
+    fn visit_if_expr(&self, expr: &IfExpr) -> Result<Output, ViskumError> {
+        if self.is_truthy(&self.evaluate(&expr.condition)?) {
+            self.evaluate(&expr.then_branch)
+        } else if let Some(else_branch) = &expr.else_branch {
+            self.evaluate(else_branch)
         } else {
-            return Err(
-                ViskumError::new(
-                    format!("A {} is not callable", callee.to_type_string()).as_str(),
-                    expr.paren.clone(),
-                    "file.vs"
-                )
-            );
+            Ok(Literal::Null)
         }
     }
+
+    fn visit_loop_expr(&self, expr: &LoopExpr) -> Result<Output, ViskumError> {
+        loop {
+            if let Err(e) = self.execute(&expr.body) {
+                match e.into_loop_control(expr.label.as_ref())? {
+                    LoopControl::Break(value) => {
+                        return Ok(value);
+                    }
+                    LoopControl::Continue => (),
+                }
+            }
+        }
+    }
+
+    fn visit_match_expr(&self, expr: &MatchExpr) -> Result<Output, ViskumError> {
+        let subject = self.evaluate(&expr.subject)?;
+
+        for arm in &expr.arms {
+            if let Some(value) = self.evaluate_arm(arm, &subject)? {
+                return Ok(value);
+            }
+        }
+
+        Err(self.no_match_error(&subject, &expr.keyword))
+    }
+
+    fn visit_range_expr(&self, expr: &RangeExpr) -> Result<Output, ViskumError> {
+        let start = self.range_bound(&expr.start, &expr.operator)?;
+        let end = self.range_bound(&expr.end, &expr.operator)?;
+
+        let step = match &expr.step {
+            Some(step) => self.range_bound(step, &expr.operator)?,
+            None => 1,
+        };
+
+        if step == 0 {
+            return Err(ViskumError::new("The step of a range can't be 0", expr.operator.clone()));
+        }
+
+        Ok(
+            Literal::Range(
+                ViskumRange::new(start, end, step, expr.operator.is(TokenType::DotDotEqual))
+            )
+        )
+    }
 }
+
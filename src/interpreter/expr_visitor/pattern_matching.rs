@@ -0,0 +1,163 @@
+use std::{ rc::Rc, cell::RefCell };
+
+use crate::{
+    environment::{ Environment, environment_value::EnvironmentValue },
+    token::{ Literal, Token },
+    error_handler::ViskumError,
+    interpreter::Interpreter,
+    pattern::{ Pattern, MatchArm },
+};
+
+use super::binary_operations;
+
+impl<'a> Interpreter<'a> {
+    /// Evaluates the arm if its pattern matches and its guard is truthy
+    pub(super) fn evaluate_arm(
+        &self,
+        arm: &MatchArm,
+        subject: &Literal
+    ) -> Result<Option<Literal>, ViskumError> {
+        let environment = self.new_child_environment();
+
+        if !self.match_pattern(&arm.pattern, subject, &environment)? {
+            return Ok(None);
+        }
+
+        self.with_environment(environment, || {
+            if let Some(guard) = &arm.guard {
+                let guard = self.evaluate(guard)?;
+
+                if !self.is_truthy(&guard) {
+                    return Ok(None);
+                }
+            }
+
+            Ok(Some(self.evaluate(&arm.body)?))
+        })
+    }
+
+    /// Checks whether the value matches the pattern, and defines the names the
+    /// pattern binds in the environment if it does
+    fn match_pattern(
+        &self,
+        pattern: &Pattern,
+        value: &Literal,
+        environment: &Rc<RefCell<Environment>>
+    ) -> Result<bool, ViskumError> {
+        match pattern {
+            Pattern::Literal(literal) => Ok(binary_operations::is_equal(literal, value)),
+            Pattern::Range { start, end, inclusive, token } => {
+                let before_end = if *inclusive {
+                    binary_operations::less_equal(value, end, token)
+                } else {
+                    binary_operations::less(value, end, token)
+                };
+
+                // Values that can't be compared with the bounds are simply not in the range
+                Ok(
+                    is_true(binary_operations::greater_equal(value, start, token)) &&
+                        is_true(before_end)
+                )
+            }
+            Pattern::Variant { enum_expr, tag, fields } => {
+                let enum_type = match self.evaluate(enum_expr)? {
+                    Literal::Enum(enum_type) => enum_type,
+                    value => {
+                        return Err(
+                            ViskumError::new(
+                                format!(
+                                    "Expected an enum before '.{}' in pattern but found a {}",
+                                    tag.lexeme,
+                                    value.to_type_string()
+                                ).as_str(),
+                                tag.clone()
+                            )
+                        );
+                    }
+                };
+
+                let index = match enum_type.find_variant(&tag.lexeme) {
+                    Some(index) => index,
+                    None => {
+                        return Err(enum_type.undefined_variant_error(tag));
+                    }
+                };
+
+                let variant = match value {
+                    Literal::Variant(variant) if variant.is_variant(&enum_type, index) => variant,
+                    _ => {
+                        return Ok(false);
+                    }
+                };
+
+                let fields = match fields {
+                    Some(fields) => fields,
+                    None => {
+                        return Ok(true);
+                    }
+                };
+
+                if fields.len() != variant.values.len() {
+                    return Err(
+                        ViskumError::new(
+                            format!(
+                                "The pattern for {}.{} has {} fields, but the variant has {}",
+                                enum_type.name,
+                                tag.lexeme,
+                                fields.len(),
+                                variant.values.len()
+                            ).as_str(),
+                            tag.clone()
+                        )
+                    );
+                }
+
+                for (field, value) in fields.iter().zip(&variant.values) {
+                    if !self.match_pattern(field, value, environment)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Pattern::Binding(token) => {
+                environment.borrow_mut().define(token, EnvironmentValue::new(value.clone(), false))?;
+                Ok(true)
+            }
+            Pattern::Wildcard => Ok(true),
+            Pattern::Or(alternatives) => {
+                for alternative in alternatives {
+                    if self.match_pattern(alternative, value, environment)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Pattern::Tuple(elements) => {
+                let tuple = match value {
+                    Literal::Tuple(tuple) if tuple.len() == elements.len() => tuple,
+                    _ => {
+                        return Ok(false);
+                    }
+                };
+
+                for (element, value) in elements.iter().zip(tuple.iter()) {
+                    if !self.match_pattern(element, value, environment)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+        }
+    }
+
+    pub(super) fn no_match_error(&self, subject: &Literal, keyword: &Token) -> ViskumError {
+        ViskumError::new(
+            format!("No match arm matches the value {}", subject.to_repr_string()).as_str(),
+            keyword.clone()
+        )
+    }
+}
+
+fn is_true(comparison: Result<Literal, ViskumError>) -> bool {
+    matches!(comparison, Ok(Literal::Bool(true)))
+}
@@ -1,14 +1,24 @@
+use std::{ rc::Rc, cell::RefCell };
+
 use crate::{
-    environment::environment_value::EnvironmentValue,
-    token::{ Literal, Token },
+    environment::{ environment_value::EnvironmentValue, Environment },
+    token::{ Literal, Token, TokenType },
     error_handler::ViskumError,
+    viskum_instance::ViskumInstance,
+    viskum_iterator::{ ViskumIterator, NativeSource, InstanceSource },
 };
 
 use super::Interpreter;
 
 impl<'a> Interpreter<'a> {
-    pub fn environment_get(&self, token: &Token) -> Result<Literal, ViskumError> {
-        (*self.environment).borrow().borrow().get(token)
+    /// Looks up a variable in the environment `depth` scopes out, as computed by
+    /// the resolver. Variables the resolver didn't find are globals
+    pub fn environment_get(
+        &self,
+        token: &Token,
+        depth: Option<usize>
+    ) -> Result<Literal, ViskumError> {
+        self.environment_at(depth).borrow().get(token)
     }
 
     pub fn environment_define(
@@ -22,8 +32,106 @@ impl<'a> Interpreter<'a> {
     pub fn environment_assign(
         &self,
         token: &Token,
-        environment_value: EnvironmentValue
+        depth: Option<usize>,
+        value: Literal
     ) -> Result<Literal, ViskumError> {
-        (*self.environment).borrow_mut().borrow_mut().assign(token, environment_value)
+        self.environment_at(depth).borrow_mut().assign(token, value)
+    }
+
+    fn environment_at(&self, depth: Option<usize>) -> Rc<RefCell<Environment>> {
+        let environment = self.environment.borrow();
+
+        match depth {
+            Some(distance) => Environment::ancestor(&environment, distance),
+            None => Environment::root(&environment),
+        }
+    }
+
+    /// The iterator a for-in loop goes through. Ranges are iterated lazily.
+    /// Other native values are copied first, so changing them inside of the
+    /// loop doesn't change what's iterated. Instances are iterated through
+    /// their iter() method, or their has_next() and next() methods
+    pub fn iterator(
+        &self,
+        iterable: &Literal,
+        token: &Token
+    ) -> Result<Rc<ViskumIterator>, ViskumError> {
+        let values: Box<dyn Iterator<Item = Literal>> = match iterable {
+            Literal::Iterator(iterator) => {
+                return Ok(iterator.clone());
+            }
+            Literal::Str(str) => {
+                let chars: Vec<Literal> = str
+                    .chars()
+                    .map(|ch| Literal::Str(ch.to_string()))
+                    .collect();
+                Box::new(chars.into_iter())
+            }
+            Literal::List(list) => Box::new(list.borrow().clone().into_iter()),
+            Literal::Tuple(tuple) => {
+                let tuple = tuple.clone();
+                Box::new((0..tuple.len()).map(move |i| tuple[i].clone()))
+            }
+            Literal::Map(map) => Box::new(map.borrow().keys().into_iter()),
+            Literal::Range(range) => Box::new(range.iter().map(Literal::Int)),
+            Literal::Instance(instance) if instance.borrow().has_method("iter") => {
+                let method = Token::new(
+                    TokenType::Identifier,
+                    "iter".to_string(),
+                    None,
+                    token.line,
+                    token.file.clone()
+                );
+
+                let iterable = match ViskumInstance::get(instance, &method)? {
+                    Literal::Func(callable) =>
+                        callable.func.call(self, &Vec::new()).map_err(|e| e.with_call_site(token))?,
+                    value => value,
+                };
+
+                // An iter() that gives back its own instance has to have
+                // has_next() and next()
+                return match &iterable {
+                    Literal::Instance(inner) if Rc::ptr_eq(inner, instance) =>
+                        self.instance_iterator(inner, token),
+                    _ => self.iterator(&iterable, token),
+                };
+            }
+            Literal::Instance(instance) => {
+                return self.instance_iterator(instance, token);
+            }
+            _ => {
+                return Err(
+                    ViskumError::new(
+                        format!("Cannot iterate over a {}", iterable.to_type_string()).as_str(),
+                        token.clone()
+                    )
+                );
+            }
+        };
+
+        Ok(Rc::new(ViskumIterator::from_source(NativeSource::new(values))))
+    }
+
+    fn instance_iterator(
+        &self,
+        instance: &Rc<RefCell<ViskumInstance>>,
+        token: &Token
+    ) -> Result<Rc<ViskumIterator>, ViskumError> {
+        let instance_ref = instance.borrow();
+
+        if !instance_ref.has_method("has_next") || !instance_ref.has_method("next") {
+            return Err(
+                ViskumError::new(
+                    format!(
+                        "Cannot iterate over a {} instance: It needs an iter() method, or has_next() and next() methods",
+                        instance_ref.class_name()
+                    ).as_str(),
+                    token.clone()
+                )
+            );
+        }
+
+        Ok(Rc::new(ViskumIterator::from_source(InstanceSource::new(instance.clone(), token))))
     }
 }
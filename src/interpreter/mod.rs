@@ -10,11 +10,13 @@ use crate::{
     error_handler::{ ErrorHandler, ViskumError },
     stmt::Stmt,
     environment::Environment,
+    viskum_module::ModuleLoader,
 };
 
 pub struct Interpreter<'a> {
     error_handler: &'a RefCell<ErrorHandler>,
     environment: &'a RefCell<Rc<RefCell<Environment>>>,
+    modules: &'a ModuleLoader,
 }
 
 type Output = Literal;
@@ -22,41 +24,43 @@ type Output = Literal;
 impl<'a> Interpreter<'a> {
     pub fn new(
         error_handler: &'a RefCell<ErrorHandler>,
-        environment: &'a RefCell<Rc<RefCell<Environment>>>
+        environment: &'a RefCell<Rc<RefCell<Environment>>>,
+        modules: &'a ModuleLoader
     ) -> Self {
         Interpreter {
             error_handler: error_handler,
             environment: environment,
+            modules: modules,
         }
     }
 
     pub fn interpret(&self, statements: Vec<Stmt>) {
-        for stmt in &statements {
-            match self.execute(stmt) {
-                Ok(_) => (),
-                Err(e) => {
-                    (*self.error_handler).borrow_mut().report_error(e);
-                    (*self.error_handler).borrow_mut().print_errors();
-                    break;
-                }
-            }
+        if let Err(e) = self.execute_statements(&statements) {
+            (*self.error_handler).borrow_mut().report_error(e);
+            (*self.error_handler).borrow_mut().print_errors();
         }
     }
 
-    fn execute(&self, stmt: &Stmt) -> Result<(), ViskumError> {
+    /// Executes the statements until one of them fails
+    pub fn execute_statements(&self, statements: &[Stmt]) -> Result<(), ViskumError> {
+        statements.iter().try_for_each(|stmt| self.execute(stmt))
+    }
+
+    pub fn execute(&self, stmt: &Stmt) -> Result<(), ViskumError> {
         stmt.accept(self)
     }
 
-    fn evaluate(&self, expr: &Expr) -> Result<Output, ViskumError> {
+    pub fn evaluate(&self, expr: &Expr) -> Result<Output, ViskumError> {
         expr.accept(self)
     }
 
-    fn is_truthy(&self, literal: &Literal) -> bool {
+    pub fn is_truthy(&self, literal: &Literal) -> bool {
         match literal {
             Literal::Bool(false) | Literal::Null => false,
             Literal::Num(x) => {
                 if *x == 0.0 { false } else { true }
             }
+            Literal::Int(x) => *x != 0,
             _ => true,
         }
     }
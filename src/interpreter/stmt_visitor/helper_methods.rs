@@ -2,9 +2,12 @@ use std::{ cell::RefCell, rc::Rc };
 
 use crate::{
     interpreter::Interpreter,
-    environment::Environment,
+    environment::{ Environment, environment_value::EnvironmentValue },
     stmt::Stmt,
-    error_handler::ViskumError,
+    expr::CallExpr,
+    token::{ Literal, Token },
+    error_handler::{ ViskumError, AbortReason },
+    viskum_callable::ViskumCallable,
 };
 
 impl<'a> Interpreter<'a> {
@@ -13,12 +16,128 @@ impl<'a> Interpreter<'a> {
         statements: &[Stmt],
         environment: Rc<RefCell<Environment>>
     ) -> Result<(), ViskumError> {
+        self.with_environment(environment, || {
+            statements.iter().try_for_each(|stmt| self.execute(stmt))
+        })
+    }
+
+    pub fn with_environment<T>(
+        &self,
+        environment: Rc<RefCell<Environment>>,
+        f: impl FnOnce() -> Result<T, ViskumError>
+    ) -> Result<T, ViskumError> {
         let previous = self.environment.replace(environment);
 
-        let result = statements.iter().try_for_each(|stmt| self.execute(stmt));
+        let result = f();
 
         self.environment.replace(previous);
 
         result
     }
+
+    pub fn new_child_environment(&self) -> Rc<RefCell<Environment>> {
+        let enclosing = self.environment.borrow().clone();
+        Rc::new(RefCell::new(Environment::new_with_enclosing(enclosing)))
+    }
+
+    /// The error a return ending with a call gives, so that the function it
+    /// returns from performs the call instead of growing the stack. Out of
+    /// line, so a return's frame stays small while its value is evaluated
+    #[inline(never)]
+    pub(super) fn tail_call(&self, call: &CallExpr, keyword: &Token) -> ViskumError {
+        match self.call_target(call) {
+            Ok((callable, arguments, _)) =>
+                ViskumError::new_with_abort(
+                    "Unexptected return statement. Must be inside of a function",
+                    keyword.clone(),
+                    AbortReason::TailCall(callable, arguments, call.paren.clone())
+                ),
+            Err(e) => e,
+        }
+    }
+
+    /// A tail call out of a try block has to be performed inside of it, so the
+    /// try can catch its errors and run finally after it
+    #[inline(never)]
+    pub(super) fn perform_tail_call(
+        &self,
+        result: Result<(), ViskumError>
+    ) -> Result<(), ViskumError> {
+        let error = match result {
+            Ok(()) => {
+                return Ok(());
+            }
+            Err(e) => e,
+        };
+
+        let (callable, arguments, call_site) = error.into_tail_call()?;
+        let value = callable.call(self, &arguments).map_err(|e| e.with_call_site(&call_site))?;
+
+        Err(
+            ViskumError::new_with_abort(
+                "Unexptected return statement. Must be inside of a function",
+                call_site,
+                AbortReason::Return(value)
+            )
+        )
+    }
+
+    /// Runs a catch block with the error bound to its name, if the error can
+    /// be caught. Like in the try body, a tail call has to be performed before
+    /// finally runs
+    #[inline(never)]
+    pub(super) fn catch(
+        &self,
+        name: &Token,
+        body: &[Stmt],
+        error: ViskumError
+    ) -> Result<(), ViskumError> {
+        let exception = error.into_exception()?;
+
+        let environment = self.new_child_environment();
+        environment.borrow_mut().define(name, EnvironmentValue::new(exception, false))?;
+
+        self.perform_tail_call(self.execute_block(body, environment))
+    }
+
+    /// The values a tuple or list holds, if there are as many as the names
+    /// they're destructured into
+    pub(super) fn destructure(
+        &self,
+        value: &Literal,
+        count: usize,
+        keyword: &Token
+    ) -> Result<Vec<Literal>, ViskumError> {
+        let values = match value {
+            Literal::Tuple(tuple) => tuple.to_vec(),
+            Literal::List(list) => list.borrow().clone(),
+            _ => {
+                return Err(
+                    ViskumError::new(
+                        format!(
+                            "Cannot destructure a {}: Only tuples and lists can be destructured",
+                            value.to_type_string()
+                        ).as_str(),
+                        keyword.clone()
+                    )
+                );
+            }
+        };
+
+        if values.len() != count {
+            return Err(
+                ViskumError::new(
+                    format!(
+                        "Cannot destructure a {} of {} values into {} names",
+                        value.to_type_string(),
+                        values.len(),
+                        count
+                    ).as_str(),
+                    keyword.clone()
+                )
+            );
+        }
+
+        Ok(values)
+    }
 }
@@ -1,14 +1,18 @@
 mod helper_methods;
 
-use std::{ rc::Rc, cell::RefCell };
+use std::{ rc::Rc, collections::HashMap };
 
 use crate::{
     stmt::*,
-    error_handler::{ ViskumError, AbortReason },
-    environment::{ environment_value::EnvironmentValue, Environment },
+    expr::Expr,
+    error_handler::{ ViskumError, AbortReason, LoopControl },
+    environment::environment_value::EnvironmentValue,
     token::{ TokenType, Literal },
     viskum_function::ViskumFunction,
-    viskum_callable::Callable,
+    viskum_class::{ ViskumClass, INITIALIZER_NAME },
+    viskum_callable::{ Callable, ViskumCallable },
+    viskum_enum::ViskumEnum,
+    viskum_exception::ViskumException,
 };
 
 use super::Interpreter;
@@ -17,11 +21,7 @@ type Output = ();
 
 impl<'a> StmtVisitor<Output> for Interpreter<'a> {
     fn visit_block_stmt(&self, stmt: &BlockStmt) -> Result<Output, ViskumError> {
-        let e = self.environment.borrow().clone();
-        self.execute_block(
-            &stmt.statements,
-            Rc::new(RefCell::new(Environment::new_with_enclosing(e)))
-        )
+        self.execute_block(&stmt.statements, self.new_child_environment())
     }
 
     fn visit_expression_stmt(&self, stmt: &ExpressionStmt) -> Result<Output, ViskumError> {
@@ -48,7 +48,20 @@ impl<'a> StmtVisitor<Output> for Interpreter<'a> {
     fn visit_let_stmt(&self, stmt: &LetStmt) -> Result<Output, ViskumError> {
         let value = self.evaluate(&stmt.initializer)?;
 
-        self.environment_define(&stmt.token, EnvironmentValue::new(value, false))?;
+        self.environment_define(&stmt.token, EnvironmentValue::new(value, stmt.mutable))?;
+
+        Ok(())
+    }
+
+    fn visit_destructure_stmt(&self, stmt: &DestructureStmt) -> Result<Output, ViskumError> {
+        let value = self.evaluate(&stmt.initializer)?;
+        let values = self.destructure(&value, stmt.names.len(), &stmt.keyword)?;
+
+        for (name, value) in stmt.names.iter().zip(values) {
+            if name.lexeme != "_" {
+                self.environment_define(name, EnvironmentValue::new(value, stmt.mutable))?;
+            }
+        }
 
         Ok(())
     }
@@ -58,12 +71,13 @@ impl<'a> StmtVisitor<Output> for Interpreter<'a> {
             match self.execute(&stmt.body) {
                 Ok(_) => (),
                 Err(e) => {
-                    if e.is_abort_error(AbortReason::Break) {
-                        break;
-                    } else if e.is_abort_error(AbortReason::Continue) {
-                        continue;
-                    } else {
-                        return Err(e);
+                    match e.into_loop_control(stmt.label.as_ref())? {
+                        LoopControl::Break(_) => {
+                            break;
+                        }
+                        LoopControl::Continue => {
+                            continue;
+                        }
                     }
                 }
             };
@@ -72,17 +86,57 @@ impl<'a> StmtVisitor<Output> for Interpreter<'a> {
         Ok(())
     }
 
-    fn visit_loop_stmt(&self, stmt: &LoopStmt) -> Result<Output, ViskumError> {
-        loop {
-            match self.execute(&stmt.body) {
+    fn visit_for_stmt(&self, stmt: &ForStmt) -> Result<Output, ViskumError> {
+        self.with_environment(self.new_child_environment(), || {
+            if let Some(initializer) = &stmt.initializer {
+                self.execute(initializer)?;
+            }
+
+            loop {
+                if let Some(condition) = &stmt.condition {
+                    if !self.is_truthy(&self.evaluate(condition)?) {
+                        break;
+                    }
+                }
+
+                match self.execute(&stmt.body) {
+                    Ok(_) => (),
+                    Err(e) => {
+                        // The increment still runs after a continue
+                        if let LoopControl::Break(_) = e.into_loop_control(stmt.label.as_ref())? {
+                            break;
+                        }
+                    }
+                }
+
+                if let Some(increment) = &stmt.increment {
+                    self.evaluate(increment)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    fn visit_forin_stmt(&self, stmt: &ForInStmt) -> Result<Output, ViskumError> {
+        let iterable = self.evaluate(&stmt.iterable)?;
+
+        let iterator = self.iterator(&iterable, &stmt.token)?;
+
+        while let Some(value) = iterator.next_value(self)? {
+            let environment = self.new_child_environment();
+            environment.borrow_mut().define(&stmt.token, EnvironmentValue::new(value, false))?;
+
+            match self.with_environment(environment, || self.execute(&stmt.body)) {
                 Ok(_) => (),
                 Err(e) => {
-                    if e.is_abort_error(AbortReason::Break) {
-                        break;
-                    } else if e.is_abort_error(AbortReason::Continue) {
-                        continue;
-                    } else {
-                        return Err(e);
+                    match e.into_loop_control(stmt.label.as_ref())? {
+                        LoopControl::Break(_) => {
+                            break;
+                        }
+                        LoopControl::Continue => {
+                            continue;
+                        }
                     }
                 }
             };
@@ -92,31 +146,36 @@ impl<'a> StmtVisitor<Output> for Interpreter<'a> {
     }
 
     fn visit_loopcontrol_stmt(&self, stmt: &LoopControlStmt) -> Result<Output, ViskumError> {
+        let label = stmt.label.as_ref().map(|label| label.lexeme.clone());
+
         match stmt.keyword.ttype {
-            TokenType::Break =>
+            TokenType::Break => {
+                let value = match &stmt.value {
+                    Some(value) => self.evaluate(value)?,
+                    None => Literal::Null,
+                };
+
                 Err(
                     ViskumError::new_with_abort(
                         "Unexpected break statement: Must be inside of a loop",
                         stmt.keyword.clone(),
-                        "file.vs",
-                        AbortReason::Break
+                        AbortReason::Break(label, value)
                     )
-                ),
+                )
+            }
             TokenType::Continue =>
                 Err(
                     ViskumError::new_with_abort(
                         "Unexpected continue statement: Must be inside of a loop",
                         stmt.keyword.clone(),
-                        "file.vs",
-                        AbortReason::Continue
+                        AbortReason::Continue(label)
                     )
                 ),
             _ =>
                 Err(
                     ViskumError::new(
                         format!("Unknown loop keyword: '{}'", stmt.keyword.lexeme).as_str(),
-                        stmt.keyword.clone(),
-                        "file.vs"
+                        stmt.keyword.clone()
                     )
                 ),
         }
@@ -124,32 +183,116 @@ impl<'a> StmtVisitor<Output> for Interpreter<'a> {
 
     fn visit_function_stmt(&self, stmt: &FunctionStmt) -> Result<Output, ViskumError> {
         let function = ViskumFunction::new(stmt.clone(), self.environment.borrow().clone());
+        let arity = function.arity();
 
         self.environment_define(
             &stmt.token,
-            EnvironmentValue::new(
-                Literal::Func(Callable { func: Rc::new(function), arity: stmt.params.len() }),
-                false
-            )
+            EnvironmentValue::new(Literal::Func(Callable { func: Rc::new(function), arity }), false)
         )?;
 
         Ok(())
     }
 
+    fn visit_class_stmt(&self, stmt: &ClassStmt) -> Result<Output, ViskumError> {
+        let mut methods = HashMap::new();
+
+        for method in &stmt.methods {
+            let function = ViskumFunction::new_method(
+                method.clone(),
+                self.environment.borrow().clone(),
+                method.token.lexeme == INITIALIZER_NAME
+            );
+
+            methods.insert(method.token.lexeme.clone(), function);
+        }
+
+        let class = ViskumClass::new(stmt.token.lexeme.clone(), methods);
+
+        self.environment_define(&stmt.token, EnvironmentValue::new(Literal::Class(class), false))?;
+
+        Ok(())
+    }
+
     fn visit_return_stmt(&self, stmt: &ReturnStmt) -> Result<Output, ViskumError> {
-        let value = if let Some(value) = &stmt.value {
-            self.evaluate(&value)?
-        } else {
-            Literal::Null
+        let value = match &stmt.value {
+            // Tail calls only carry positional arguments
+            Some(Expr::Call(call)) if call.named.is_empty() => {
+                return Err(self.tail_call(call, &stmt.keyword));
+            }
+            Some(value) => self.evaluate(value)?,
+            None => Literal::Null,
         };
 
         Err(
             ViskumError::new_with_abort(
                 "Unexptected return statement. Must be inside of a function",
                 stmt.keyword.clone(),
-                "file.vs",
                 AbortReason::Return(value)
             )
         )
     }
+
+    fn visit_yield_stmt(&self, stmt: &YieldStmt) -> Result<Output, ViskumError> {
+        // Generators run their yields themselves, and the resolver doesn't
+        // allow them anywhere else
+        Err(
+            ViskumError::new(
+                "Unexpected yield statement: Must be inside of a generator",
+                stmt.keyword.clone()
+            )
+        )
+    }
+
+    fn visit_enum_stmt(&self, stmt: &EnumStmt) -> Result<Output, ViskumError> {
+        let enum_type = ViskumEnum::new(stmt.token.lexeme.clone(), stmt.variants.clone());
+
+        self.environment_define(&stmt.token, EnvironmentValue::new(Literal::Enum(enum_type), false))?;
+
+        Ok(())
+    }
+
+    fn visit_import_stmt(&self, stmt: &ImportStmt) -> Result<Output, ViskumError> {
+        let module = self.modules.import(&stmt.path, &stmt.name.lexeme, &stmt.keyword)?;
+
+        self.environment_define(&stmt.name, EnvironmentValue::new(Literal::Module(module), false))?;
+
+        Ok(())
+    }
+
+    fn visit_throw_stmt(&self, stmt: &ThrowStmt) -> Result<Output, ViskumError> {
+        let (message, exception) = match self.evaluate(&stmt.value)? {
+            // Rethrowing a caught error keeps where it came from
+            Literal::Exception(exception) =>
+                (exception.message.clone(), Literal::Exception(exception)),
+            value => {
+                let message = value.to_string();
+                (message.clone(), ViskumException::new_literal(message, stmt.keyword.line, value))
+            }
+        };
+
+        Err(
+            ViskumError::new_with_abort(
+                format!("Uncaught error: {}", message).as_str(),
+                stmt.keyword.clone(),
+                AbortReason::Throw(exception)
+            )
+        )
+    }
+
+    fn visit_try_stmt(&self, stmt: &TryStmt) -> Result<Output, ViskumError> {
+        let result = self.execute_block(&stmt.body, self.new_child_environment());
+        let mut result = self.perform_tail_call(result);
+
+        if let (Some(name), Some(body)) = (&stmt.catch_name, &stmt.catch_body) {
+            if let Err(e) = result {
+                result = self.catch(name, body, e);
+            }
+        }
+
+        if let Some(body) = &stmt.finally_body {
+            self.execute_block(body, self.new_child_environment())?;
+        }
+
+        result
+    }
 }
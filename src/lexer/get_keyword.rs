@@ -7,6 +7,7 @@ pub fn get_keyword(check: String) -> Option<TokenType> {
         "else" => Some(TokenType::Else),
         "false" => Some(TokenType::False),
         "for" => Some(TokenType::For),
+        "in" => Some(TokenType::In),
         "if" => Some(TokenType::If),
         "null" => Some(TokenType::Null),
         "or" => Some(TokenType::Or),
@@ -22,6 +23,15 @@ pub fn get_keyword(check: String) -> Option<TokenType> {
         "continue" => Some(TokenType::Continue),
         "loop" => Some(TokenType::Loop),
         "fn" => Some(TokenType::Fn),
+        "match" => Some(TokenType::Match),
+        "enum" => Some(TokenType::Enum),
+        "import" => Some(TokenType::Import),
+        "as" => Some(TokenType::As),
+        "throw" => Some(TokenType::Throw),
+        "try" => Some(TokenType::Try),
+        "catch" => Some(TokenType::Catch),
+        "finally" => Some(TokenType::Finally),
+        "yield" => Some(TokenType::Yield),
         _ => None,
     }
 }
@@ -12,9 +12,68 @@ impl<'a> Lexer<'a> {
     }
 
     pub(super) fn add_token_literal(&mut self, ttype: TokenType, literal: Option<Literal>) {
+        if self.statement_ended {
+            self.statement_ended = false;
+
+            if !continues_statement(&ttype) {
+                self.add_newline_token();
+            }
+        }
+
+        match ttype {
+            TokenType::LeftParen |
+            TokenType::LeftBracket |
+            TokenType::LeftBrace |
+            TokenType::Interpolation => self.groupings.push(ttype.clone()),
+            TokenType::RightParen | TokenType::RightBracket | TokenType::RightBrace => {
+                self.groupings.pop();
+            }
+            _ => (),
+        }
+
         let lexeme: String = self.source[self.start..self.current].iter().collect();
 
-        self.tokens.push(Token::new(ttype, lexeme, literal, self.line))
+        self.tokens.push(Token::new(ttype, lexeme, literal, self.line, self.file.clone()))
+    }
+
+    /// A newline ends a statement when the line ends in a token that can end
+    /// one, unless it's inside of parentheses, brackets or an interpolation
+    pub(super) fn ends_statement(&self) -> bool {
+        if !matches!(self.groupings.last(), None | Some(TokenType::LeftBrace)) {
+            return false;
+        }
+
+        match self.tokens.last() {
+            Some(token) =>
+                matches!(
+                    token.ttype,
+                    TokenType::Identifier |
+                        TokenType::Label |
+                        TokenType::String |
+                        TokenType::Number |
+                        TokenType::True |
+                        TokenType::False |
+                        TokenType::Null |
+                        TokenType::This |
+                        TokenType::Return |
+                        TokenType::Yield |
+                        TokenType::Break |
+                        TokenType::Continue |
+                        TokenType::RightParen |
+                        TokenType::RightBracket |
+                        TokenType::RightBrace |
+                        TokenType::Increment |
+                        TokenType::Decrement |
+                        TokenType::Factorial
+                ),
+            None => false,
+        }
+    }
+
+    fn add_newline_token(&mut self) {
+        let line = self.tokens.last().map_or(self.line, |token| token.line);
+
+        self.tokens.push(Token::newline(line, self.file.clone()))
     }
 
     pub(super) fn is_at_end(&self) -> bool {
@@ -75,8 +134,7 @@ impl<'a> Lexer<'a> {
                         self.error_handler,
                         ViskumError::new(
                             "Expected '*/'",
-                            Token::invalid(Some(self.line)),
-                            "file.vs"
+                            Token::invalid(Some(self.line))
                         )
                     );
                     break;
@@ -86,37 +144,178 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Scans the rest of a string literal. The string is also resumed from here
+    /// after the closing '}' of an interpolated expression.
+    ///
+    /// Every part that's followed by an embedded expression becomes an
+    /// Interpolation token, and the tokens of the expression are scanned as usual
+    /// until the matching '}'. The last part becomes a String token.
     pub(super) fn string(&mut self) {
+        let mut value = String::new();
+
         while let Some(ch) = self.peek() {
-            if ch == '"' {
-                break;
-            } else if ch == '\n' {
-                self.increment_line();
+            match ch {
+                '"' => {
+                    break;
+                }
+                '{' if self.peek_next() == Some('{') => {
+                    self.advance();
+                }
+                '}' if self.peek_next() == Some('}') => {
+                    self.advance();
+                }
+                '{' => {
+                    self.advance();
+                    self.add_token_literal(TokenType::Interpolation, Some(Literal::Str(value)));
+                    self.interpolations.push(0);
+                    return;
+                }
+                '\\' => {
+                    self.advance();
+                    if let Some(escaped) = self.escape_sequence() {
+                        value.push(escaped);
+                    }
+                    continue;
+                }
+                '\n' => self.increment_line(),
+                _ => (),
             }
+            value.push(ch);
             self.advance();
         }
 
         if self.is_at_end() {
             report_error(
                 self.error_handler,
-                ViskumError::new("Unterminated string", Token::invalid(Some(self.line)), "file.vs")
+                ViskumError::new("Unterminated string", Token::invalid(Some(self.line)))
             );
             return;
         }
 
         self.advance();
 
-        let value: String = self.source[self.start + 1..self.current - 1].iter().collect();
+        self.add_token_literal(TokenType::String, Some(Literal::Str(value)))
+    }
+
+    /// Scans the escape sequence after a '\\'. Invalid escapes are reported and skipped
+    fn escape_sequence(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.advance();
+
+        let escaped = match ch {
+            'n' => Some('\n'),
+            't' => Some('\t'),
+            'r' => Some('\r'),
+            '0' => Some('\0'),
+            '"' => Some('"'),
+            '\\' => Some('\\'),
+            '{' => Some('{'),
+            '}' => Some('}'),
+            'u' => self.unicode_escape(),
+            _ => None,
+        };
+
+        if escaped.is_none() {
+            let sequence = if ch == 'u' { "\\u{...}".to_string() } else { format!("\\{}", ch) };
+
+            report_error(
+                self.error_handler,
+                ViskumError::new(
+                    format!("Invalid escape sequence '{}' in string", sequence).as_str(),
+                    Token::invalid(Some(self.line))
+                )
+            );
+        }
+
+        escaped
+    }
+
+    /// Scans the '{1F600}' part of a '\\u{1F600}' escape
+    fn unicode_escape(&mut self) -> Option<char> {
+        if !self.match_char('{') {
+            return None;
+        }
+
+        let mut digits = String::new();
+        while let Some(ch) = self.peek() {
+            if ch == '}' || ch == '"' || digits.len() > 6 {
+                break;
+            }
+            digits.push(ch);
+            self.advance();
+        }
+
+        if !self.match_char('}') || digits.is_empty() || digits.len() > 6 {
+            return None;
+        }
+
+        u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32)
+    }
+
+    /// Scans a raw string like r"C:\\path" or r#"say "hi""#. Raw strings have no
+    /// escape sequences or interpolation, and end at a '"' followed by as many
+    /// '#' as they started with
+    pub(super) fn raw_string(&mut self) {
+        let mut hashes = 0;
+        while self.match_char('#') {
+            hashes += 1;
+        }
+
+        if !self.match_char('"') {
+            report_error(
+                self.error_handler,
+                ViskumError::new(
+                    "Expected '\"' to start raw string",
+                    Token::invalid(Some(self.line))
+                )
+            );
+            return;
+        }
+
+        let value_start = self.current;
+
+        loop {
+            match self.peek() {
+                Some('"') if self.closes_raw_string(hashes) => {
+                    break;
+                }
+                Some('\n') => self.increment_line(),
+                Some(_) => (),
+                None => {
+                    report_error(
+                        self.error_handler,
+                        ViskumError::new(
+                            "Unterminated raw string",
+                            Token::invalid(Some(self.line))
+                        )
+                    );
+                    return;
+                }
+            }
+            self.advance();
+        }
+
+        let value: String = self.source[value_start..self.current].iter().collect();
+
+        for _ in 0..=hashes {
+            self.advance();
+        }
 
         self.add_token_literal(TokenType::String, Some(Literal::Str(value)))
     }
 
+    fn closes_raw_string(&self, hashes: usize) -> bool {
+        (1..=hashes).all(|offset| self.source.get(self.current + offset) == Some(&'#'))
+    }
+
     pub(super) fn number(&mut self) {
         while is_digit(self.peek()) {
             self.advance();
         }
 
-        if self.peek() == Some('.') && is_digit(self.peek_next()) {
+        let is_float = self.peek() == Some('.') && is_digit(self.peek_next());
+
+        if is_float {
             self.advance();
 
             while is_digit(self.peek()) {
@@ -125,9 +324,26 @@ impl<'a> Lexer<'a> {
         }
 
         let value: String = self.source[self.start..self.current].iter().collect();
-        let num: f64 = value.parse().unwrap();
 
-        self.add_token_literal(TokenType::Number, Some(Literal::Num(num)));
+        if is_float {
+            let num: f64 = value.parse().unwrap();
+
+            self.add_token_literal(TokenType::Number, Some(Literal::Num(num)));
+        } else {
+            match value.parse::<i64>() {
+                Ok(int) => self.add_token_literal(TokenType::Number, Some(Literal::Int(int))),
+                Err(_) => {
+                    report_error(
+                        self.error_handler,
+                        ViskumError::new(
+                            format!("Integer literal {} is too large for an int", value).as_str(),
+                            Token::invalid(Some(self.line))
+                        )
+                    );
+                    self.add_token_literal(TokenType::Number, Some(Literal::Int(0)));
+                }
+            }
+        }
 
         if self.peek() == Some('!') {
             self.start = self.current;
@@ -159,4 +375,32 @@ impl<'a> Lexer<'a> {
             self.add_token(TokenType::Factorial)
         }
     }
+
+    /// A loop label like 'outer. The lexeme keeps the quote
+    pub(super) fn label(&mut self) {
+        while let Some(ch) = self.peek() {
+            if is_alphabetic(Some(ch)) || is_digit(Some(ch)) {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+
+        self.add_token(TokenType::Label);
+    }
+}
+
+/// Tokens that continue the statement on the line before them, like a method
+/// call on a new line, an else after the closing brace of an if, or the next
+/// step of a pipeline
+fn continues_statement(ttype: &TokenType) -> bool {
+    matches!(
+        ttype,
+        TokenType::Dot |
+            TokenType::RightBrace |
+            TokenType::Else |
+            TokenType::Catch |
+            TokenType::Finally |
+            TokenType::PipeGreater
+    )
 }
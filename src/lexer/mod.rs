@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::{ cell::RefCell, rc::Rc };
 
 mod get_keyword;
 mod helper_methods;
@@ -15,11 +15,18 @@ pub struct Lexer<'a> {
     current: usize,
     line: usize,
     line_position: usize,
+    file: Rc<str>,
+    // Brace depth inside each string interpolation that's currently open
+    interpolations: Vec<usize>,
+    // The brackets and interpolations that are currently open
+    groupings: Vec<TokenType>,
+    // Whether the last line ended in a way that ends the statement on it
+    statement_ended: bool,
     error_handler: &'a RefCell<ErrorHandler>,
 }
 
 impl<'a> Lexer<'a> {
-    pub fn new(source: String, error_handler: &'a RefCell<ErrorHandler>) -> Self {
+    pub fn new(source: String, file: &str, error_handler: &'a RefCell<ErrorHandler>) -> Self {
         Lexer {
             source: source.chars().collect(),
             tokens: Vec::new(),
@@ -27,6 +34,10 @@ impl<'a> Lexer<'a> {
             current: 0,
             line: 1,
             line_position: 0,
+            file: Rc::from(file),
+            interpolations: Vec::new(),
+            groupings: Vec::new(),
+            statement_ended: false,
             error_handler: error_handler,
         }
     }
@@ -38,7 +49,17 @@ impl<'a> Lexer<'a> {
             self.scan_token();
         }
 
-        self.tokens.push(Token::eof(self.line));
+        if !self.interpolations.is_empty() {
+            report_error(
+                self.error_handler,
+                ViskumError::new(
+                    "Unterminated string interpolation",
+                    Token::invalid(Some(self.line))
+                )
+            );
+        }
+
+        self.tokens.push(Token::eof(self.line, self.file.clone()));
 
         Ok(&self.tokens)
     }
@@ -62,10 +83,49 @@ impl<'a> Lexer<'a> {
                     self.add_token(TokenType::Factorial)
                 }
             }
-            '{' => self.add_token(TokenType::LeftBrace),
-            '}' => self.add_token(TokenType::RightBrace),
+            '{' => {
+                if let Some(depth) = self.interpolations.last_mut() {
+                    *depth += 1;
+                }
+                self.add_token(TokenType::LeftBrace)
+            }
+            '}' => {
+                match self.interpolations.last_mut() {
+                    Some(0) => {
+                        self.interpolations.pop();
+                        self.groupings.pop();
+                        self.string()
+                    }
+                    Some(depth) => {
+                        *depth -= 1;
+                        self.add_token(TokenType::RightBrace)
+                    }
+                    None => self.add_token(TokenType::RightBrace),
+                }
+            }
+            '[' => self.add_token(TokenType::LeftBracket),
+            ']' => {
+                self.add_token(TokenType::RightBracket);
+                if self.peek() == Some('!') {
+                    self.start = self.current;
+                    self.advance();
+                    self.add_token(TokenType::Factorial)
+                }
+            }
             ',' => self.add_token(TokenType::Comma),
-            '.' => self.add_token(TokenType::Dot),
+            '.' => {
+                if self.match_char('.') {
+                    if self.match_char('.') {
+                        self.add_token(TokenType::Ellipsis)
+                    } else if self.match_char('=') {
+                        self.add_token(TokenType::DotDotEqual)
+                    } else {
+                        self.add_token(TokenType::DotDot)
+                    }
+                } else {
+                    self.add_token(TokenType::Dot)
+                }
+            }
             '-' => {
                 if self.match_char('-') {
                     self.add_token(TokenType::Decrement)
@@ -99,6 +159,40 @@ impl<'a> Lexer<'a> {
                     self.add_token(TokenType::Power)
                 }
             }
+            '%' => {
+                if self.match_char('=') {
+                    self.add_token(TokenType::PercentEqual)
+                } else {
+                    self.add_token(TokenType::Percent)
+                }
+            }
+            '~' => {
+                if self.match_char('/') {
+                    if self.match_char('=') {
+                        self.add_token(TokenType::TildeSlashEqual)
+                    } else {
+                        self.add_token(TokenType::TildeSlash)
+                    }
+                } else {
+                    self.add_token(TokenType::Tilde)
+                }
+            }
+            '&' => {
+                if self.match_char('=') {
+                    self.add_token(TokenType::AmpersandEqual)
+                } else {
+                    self.add_token(TokenType::Ampersand)
+                }
+            }
+            '|' => {
+                if self.match_char('=') {
+                    self.add_token(TokenType::PipeEqual)
+                } else if self.match_char('>') {
+                    self.add_token(TokenType::PipeGreater)
+                } else {
+                    self.add_token(TokenType::Pipe)
+                }
+            }
             ':' => self.add_token(TokenType::Colon),
             '?' => self.add_token(TokenType::QuestionMark),
             '!' => {
@@ -111,19 +205,33 @@ impl<'a> Lexer<'a> {
             '=' => {
                 if self.match_char('=') {
                     self.add_token(TokenType::EqualEqual)
+                } else if self.match_char('>') {
+                    self.add_token(TokenType::FatArrow)
                 } else {
                     self.add_token(TokenType::Equal)
                 }
             }
             '<' => {
-                if self.match_char('=') {
+                if self.match_char('<') {
+                    if self.match_char('=') {
+                        self.add_token(TokenType::ShiftLeftEqual)
+                    } else {
+                        self.add_token(TokenType::ShiftLeft)
+                    }
+                } else if self.match_char('=') {
                     self.add_token(TokenType::LessEqual)
                 } else {
                     self.add_token(TokenType::Less)
                 }
             }
             '>' => {
-                if self.match_char('=') {
+                if self.match_char('>') {
+                    if self.match_char('=') {
+                        self.add_token(TokenType::ShiftRightEqual)
+                    } else {
+                        self.add_token(TokenType::ShiftRight)
+                    }
+                } else if self.match_char('=') {
                     self.add_token(TokenType::GreaterEqual)
                 } else {
                     self.add_token(TokenType::Greater)
@@ -146,8 +254,13 @@ impl<'a> Lexer<'a> {
                 }
             }
             ' ' | '\r' | '\t' => (),
-            '\n' => self.increment_line(),
+            '\n' => {
+                self.statement_ended = self.ends_statement();
+                self.increment_line()
+            }
             '"' => self.string(),
+            '\'' if is_alphabetic(self.peek()) => self.label(),
+            'r' if self.peek() == Some('"') || self.peek() == Some('#') => self.raw_string(),
             '0'..='9' => self.number(),
             _ => {
                 if is_alphabetic(Some(c)) {
@@ -157,8 +270,7 @@ impl<'a> Lexer<'a> {
                         self.error_handler,
                         ViskumError::new(
                             format!("Unrecognizable character: {}", c).as_str(),
-                            Token::invalid(Some(self.line)),
-                            "file.vs"
+                            Token::invalid(Some(self.line))
                         )
                     )
                 }
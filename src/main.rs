@@ -5,13 +5,25 @@ mod lexer;
 mod token;
 mod parser;
 mod interpreter;
+mod resolver;
 mod util;
 mod expr;
 mod stmt;
 mod ast_printer;
+mod pattern;
 mod environment;
 mod viskum_callable;
 mod viskum_function;
+mod viskum_class;
+mod viskum_instance;
+mod viskum_map;
+mod viskum_enum;
+mod viskum_module;
+mod viskum_exception;
+mod viskum_range;
+mod viskum_iterator;
+mod viskum_generator;
+mod viskum_partial;
 
 use print_util::print_error;
 use run::Viskum;
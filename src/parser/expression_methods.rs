@@ -1,9 +1,10 @@
-use std::borrow::Borrow;
+use std::{ borrow::Borrow, cell::Cell, rc::Rc };
 
 use crate::{
     expr::{
         Expr,
         BinaryExpr,
+        RangeExpr,
         PrefixExpr,
         LiteralExpr,
         GroupingExpr,
@@ -13,10 +14,37 @@ use crate::{
         AssignExpr,
         LogicalExpr,
         CallExpr,
+        PartialExpr,
+        PlaceholderExpr,
+        GetExpr,
+        SetExpr,
+        ThisExpr,
+        NewExpr,
+        ListExpr,
+        TupleExpr,
+        MapExpr,
+        BlockExpr,
+        IfExpr,
+        LoopExpr,
+        IndexExpr,
+        IndexSetExpr,
+        LambdaExpr,
+        InterpolationExpr,
     },
     error_handler::ViskumError,
     token::{ TokenType, Literal, Token },
-    stmt::{ Stmt, LetStmt, FunctionStmt },
+    stmt::{
+        Stmt,
+        LetStmt,
+        DestructureStmt,
+        FunctionStmt,
+        ClassStmt,
+        ReturnStmt,
+        EnumStmt,
+        ExpressionStmt,
+    },
+    viskum_enum::VariantDeclaration,
+    viskum_function::{ Parameter, NamedArgument },
     util::report_error,
 };
 
@@ -24,6 +52,13 @@ use super::Parser;
 
 impl<'a> Parser<'a> {
     pub(super) fn variable_declaration(&mut self) -> Result<Stmt, ViskumError> {
+        let keyword = self.peek_previous()?;
+        let mutable = self.match_tokens(&[TokenType::Mut])?;
+
+        if self.match_tokens(&[TokenType::LeftParen])? {
+            return self.destructuring_declaration(keyword, mutable);
+        }
+
         let token = self.consume_and_get(TokenType::Identifier, "Expected variable name")?;
 
         let initializer = if self.match_tokens(&[TokenType::Equal])? {
@@ -32,19 +67,337 @@ impl<'a> Parser<'a> {
             Expr::Literal(LiteralExpr { value: Some(Literal::Null) })
         };
 
-        self.consume(TokenType::Semicolon, "Expected ';' after variable declaration")?;
+        self.consume_end_of_statement("Expected ';' after variable declaration")?;
 
-        Ok(Stmt::Let(LetStmt { token: token, initializer: initializer }))
+        Ok(Stmt::Let(LetStmt { token: token, initializer: initializer, mutable: mutable }))
+    }
+
+    /// let (a, b) = value, where _ skips a value
+    fn destructuring_declaration(&mut self, keyword: Token, mutable: bool) -> Result<Stmt, ViskumError> {
+        let mut names: Vec<Token> = Vec::new();
+
+        loop {
+            let name = self.consume_and_get(TokenType::Identifier, "Expected variable name in destructuring pattern")?;
+
+            if name.lexeme != "_" && names.iter().any(|other| other.lexeme == name.lexeme) {
+                return Err(
+                    ViskumError::new(
+                        format!("Variable '{}' is declared twice in the pattern", name.lexeme).as_str(),
+                        name
+                    )
+                );
+            }
+            names.push(name);
+
+            if !self.match_tokens(&[TokenType::Comma])? || self.check(&TokenType::RightParen)? {
+                break;
+            }
+        }
+
+        self.consume(TokenType::RightParen, "Expected ')' after destructured names")?;
+        self.consume(TokenType::Equal, "Expected '=' after destructuring pattern")?;
+
+        let initializer = self.expression()?;
+
+        self.consume_end_of_statement("Expected ';' after variable declaration")?;
+
+        Ok(Stmt::Destructure(DestructureStmt { keyword, names, initializer, mutable }))
     }
 
     pub(super) fn function_declaration(&mut self, kind: String) -> Result<Stmt, ViskumError> {
+        Ok(Stmt::Function(self.function(kind)?))
+    }
+
+    pub(super) fn class_declaration(&mut self) -> Result<Stmt, ViskumError> {
+        let token = self.consume_and_get(TokenType::Identifier, "Expected class name")?;
+        self.consume(TokenType::LeftBrace, "Expected '{' before class body")?;
+
+        let mut methods: Vec<FunctionStmt> = Vec::new();
+
+        while !self.check(&TokenType::RightBrace)? && !self.is_at_end()? {
+            if self.match_tokens(&[TokenType::Semicolon])? {
+                continue;
+            }
+            self.consume(TokenType::Fn, "Expected method declaration in class body")?;
+            methods.push(self.function("method".to_string())?);
+        }
+
+        self.consume(TokenType::RightBrace, "Expected '}' after class body")?;
+
+        Ok(Stmt::Class(ClassStmt { token: token, methods: methods }))
+    }
+
+    pub(super) fn enum_declaration(&mut self) -> Result<Stmt, ViskumError> {
+        let token = self.consume_and_get(TokenType::Identifier, "Expected enum name")?;
+        self.consume(TokenType::LeftBrace, "Expected '{' before enum body")?;
+
+        let mut variants: Vec<VariantDeclaration> = Vec::new();
+
+        while !self.check(&TokenType::RightBrace)? && !self.is_at_end()? {
+            let variant = self.consume_and_get(TokenType::Identifier, "Expected variant name")?;
+            self.report_if_duplicate(
+                &variant,
+                variants.iter().map(|variant| &variant.token),
+                "Variant"
+            );
+
+            let mut fields: Vec<Token> = Vec::new();
+
+            if self.match_tokens(&[TokenType::LeftParen])? {
+                if !self.check(&TokenType::RightParen)? {
+                    loop {
+                        let field = self.consume_and_get(
+                            TokenType::Identifier,
+                            "Expected field name"
+                        )?;
+                        self.report_if_duplicate(&field, fields.iter(), "Field");
+                        fields.push(field);
+
+                        if !self.match_tokens(&[TokenType::Comma])? {
+                            break;
+                        }
+                    }
+                }
+                self.consume(TokenType::RightParen, "Expected ')' after variant fields")?;
+            }
+
+            variants.push(VariantDeclaration { token: variant, fields });
+
+            // Variants are separated by commas or newlines
+            if !self.match_tokens(&[TokenType::Comma, TokenType::Semicolon])? {
+                break;
+            }
+        }
+
+        self.consume(TokenType::RightBrace, "Expected '}' after enum variants")?;
+
+        Ok(Stmt::Enum(EnumStmt { token, variants }))
+    }
+
+    fn report_if_duplicate<'b>(
+        &self,
+        token: &Token,
+        mut previous: impl Iterator<Item = &'b Token>,
+        kind: &str
+    ) {
+        if previous.any(|previous| previous.lexeme == token.lexeme) {
+            report_error(
+                self.error_handler,
+                ViskumError::new(
+                    format!("{} '{}' is declared more than once", kind, token.lexeme).as_str(),
+                    token.clone()
+                )
+            );
+        }
+    }
+
+    fn function(&mut self, kind: String) -> Result<FunctionStmt, ViskumError> {
         let function_token = self.consume_and_get(
             TokenType::Identifier,
             format!("Expected {} name", kind).as_str()
         )?;
         self.consume(TokenType::LeftParen, format!("Expected '(' after {} name", kind).as_str())?;
 
-        let mut params: Vec<Token> = Vec::new();
+        let params = self.parameters(&kind)?;
+
+        self.consume(TokenType::LeftBrace, format!("Expected '{{' before {} body", kind).as_str())?;
+
+        let (body, generator) = self.function_body()?;
+
+        Ok(FunctionStmt {
+            token: function_token,
+            params,
+            body: Rc::from(body),
+            generator,
+        })
+    }
+
+    /// Parses the block of a function, and whether it yields, which makes the
+    /// function a generator
+    fn function_body(&mut self) -> Result<(Vec<Stmt>, bool), ViskumError> {
+        let enclosing_yields = std::mem::replace(&mut self.function_yields, false);
+        let body = self.block();
+        let generator = std::mem::replace(&mut self.function_yields, enclosing_yields);
+
+        Ok((body?, generator))
+    }
+
+    /// Parses the statements of a block after its '{'. An expression at the end
+    /// that isn't followed by an explicit ';' is the value of the block
+    pub(super) fn block_expression(&mut self) -> Result<Expr, ViskumError> {
+        let mut statements: Vec<Stmt> = Vec::new();
+        let mut value = None;
+
+        while !self.check(&TokenType::RightBrace)? && !self.is_at_end()? {
+            if self.match_tokens(&[TokenType::Semicolon])? {
+                continue;
+            }
+
+            if self.starts_statement()? {
+                statements.push(self.declaration()?);
+                continue;
+            }
+
+            let expression = self.expression()?;
+
+            let ends_block =
+                self.check(&TokenType::RightBrace)? ||
+                (self.check(&TokenType::Semicolon)? &&
+                    self.peek()?.inferred &&
+                    self.check_next(&TokenType::RightBrace)?);
+
+            if ends_block {
+                self.match_tokens(&[TokenType::Semicolon])?;
+                value = Some(Box::from(expression));
+                break;
+            }
+
+            // Like their statement forms, these don't need a ';' after their '}'
+            if matches!(expression, Expr::Block(_) | Expr::If(_) | Expr::Match(_) | Expr::Loop(_)) {
+                self.match_tokens(&[TokenType::Semicolon])?;
+            } else {
+                self.consume_end_of_statement("Expected ';' after expression")?;
+            }
+
+            statements.push(Stmt::Expression(ExpressionStmt { expression }));
+        }
+
+        self.consume(TokenType::RightBrace, "Expected '}' after block")?;
+
+        Ok(Expr::Block(BlockExpr { statements, value }))
+    }
+
+    /// if cond { a } else { b }, which is null when the condition is false and
+    /// there's no else
+    fn if_expression(&mut self) -> Result<Expr, ViskumError> {
+        let condition = self.expression()?;
+
+        self.consume(TokenType::LeftBrace, "Expected '{' after condition")?;
+        let then_branch = self.block_expression()?;
+
+        let else_branch = if self.match_tokens(&[TokenType::Else])? {
+            if self.match_tokens(&[TokenType::If])? {
+                Some(Box::from(self.if_expression()?))
+            } else {
+                self.consume(TokenType::LeftBrace, "Expected '{' after else")?;
+                Some(Box::from(self.block_expression()?))
+            }
+        } else {
+            None
+        };
+
+        Ok(
+            Expr::If(IfExpr {
+                condition: Box::from(condition),
+                then_branch: Box::from(then_branch),
+                else_branch,
+            })
+        )
+    }
+
+    /// loop { ... }, which is the value given to the break that ends it
+    pub(super) fn loop_expression(&mut self, label: Option<Token>) -> Result<Expr, ViskumError> {
+        self.ensure(TokenType::LeftBrace, "Expected '{' after loop keyword")?;
+
+        let body = self.statement()?;
+
+        Ok(Expr::Loop(LoopExpr { label, body: Rc::from(body) }))
+    }
+
+    /// Whether the next token starts a statement that has no value, and can't
+    /// end a block expression
+    fn starts_statement(&self) -> Result<bool, ViskumError> {
+        if self.check(&TokenType::Fn)? {
+            return self.check_next(&TokenType::Identifier);
+        }
+
+        // Labeled while and for loops, while a labeled loop is an expression
+        if self.check(&TokenType::Label)? {
+            return Ok(
+                !self.tokens
+                    .get(self.current + 2)
+                    .is_some_and(|token| token.is(TokenType::Loop))
+            );
+        }
+
+        Ok(
+            matches!(
+                self.peek()?.ttype,
+                TokenType::Let |
+                    TokenType::Class |
+                    TokenType::Enum |
+                    TokenType::Import |
+                    TokenType::Return |
+                    TokenType::Yield |
+                    TokenType::While |
+                    TokenType::For |
+                    TokenType::Break |
+                    TokenType::Continue |
+                    TokenType::Print |
+                    TokenType::Throw |
+                    TokenType::Try
+            )
+        )
+    }
+
+    fn lambda(&mut self) -> Result<Expr, ViskumError> {
+        let keyword = self.peek_previous()?;
+        self.consume(TokenType::LeftParen, "Expected '(' after 'fn'")?;
+
+        let params = self.parameters("lambda")?;
+
+        // fn(x) => x * 2 is short for fn(x) { return x * 2; }
+        let (body, generator) = if self.match_tokens(&[TokenType::FatArrow])? {
+            let arrow = self.peek_previous()?;
+            let value = self.expression()?;
+
+            (vec![Stmt::Return(ReturnStmt { keyword: arrow, value: Some(value) })], false)
+        } else {
+            self.consume(TokenType::LeftBrace, "Expected '{' or '=>' before lambda body")?;
+            self.function_body()?
+        };
+
+        Ok(
+            Expr::Lambda(LambdaExpr {
+                declaration: FunctionStmt { token: keyword, params, body: Rc::from(body), generator },
+            })
+        )
+    }
+
+    fn interpolation(&mut self) -> Result<Expr, ViskumError> {
+        let token = self.peek_previous()?;
+        let mut parts = vec![Self::string_part(&token)];
+        let mut expressions = Vec::new();
+
+        loop {
+            expressions.push(self.expression()?);
+
+            if self.match_tokens(&[TokenType::Interpolation])? {
+                parts.push(Self::string_part(&self.peek_previous()?));
+            } else {
+                let end = self.consume_and_get(
+                    TokenType::String,
+                    "Expected '}' after interpolated expression"
+                )?;
+                parts.push(Self::string_part(&end));
+                break;
+            }
+        }
+
+        Ok(Expr::Interpolation(InterpolationExpr { parts, expressions }))
+    }
+
+    fn string_part(token: &Token) -> String {
+        match &token.literal {
+            Some(Literal::Str(str)) => str.clone(),
+            _ => String::new(),
+        }
+    }
+
+    /// Parameters are names, optionally with a default value like
+    /// greeting = "hi". The last one can be a rest parameter like ...others
+    fn parameters(&mut self, kind: &str) -> Result<Vec<Parameter>, ViskumError> {
+        let mut params: Vec<Parameter> = Vec::new();
 
         if !self.check(&TokenType::RightParen)? {
             loop {
@@ -53,15 +406,38 @@ impl<'a> Parser<'a> {
                         self.error_handler,
                         ViskumError::new(
                             "Cannot have more than 255 parameters",
-                            self.peek()?,
-                            "file.vs"
+                            self.peek()?
                         )
                     );
                 }
 
-                params.push(
-                    self.consume_and_get(TokenType::Identifier, "Expected parameter name")?
-                );
+                let rest = self.match_tokens(&[TokenType::Ellipsis])?;
+                let token = self.consume_and_get(TokenType::Identifier, "Expected parameter name")?;
+
+                let default = if !rest && self.match_tokens(&[TokenType::Equal])? {
+                    Some(self.expression()?)
+                } else {
+                    None
+                };
+
+                if default.is_none() && !rest && params.iter().any(|param| param.default.is_some()) {
+                    report_error(
+                        self.error_handler,
+                        ViskumError::new(
+                            format!(
+                                "Parameter '{}' needs a default value, because the parameters before it have one",
+                                token.lexeme
+                            ).as_str(),
+                            token.clone()
+                        )
+                    );
+                }
+
+                params.push(Parameter { token, default, rest });
+
+                if rest {
+                    break;
+                }
 
                 if !self.match_tokens(&[TokenType::Comma])? {
                     break;
@@ -72,25 +448,18 @@ impl<'a> Parser<'a> {
             TokenType::RightParen,
             format!("Expected ')' after {} parameters", kind).as_str()
         )?;
-        self.consume(TokenType::LeftBrace, format!("Expected '{{' before {} body", kind).as_str())?;
 
-        let body = self.block()?;
-
-        Ok(
-            Stmt::Function(FunctionStmt {
-                token: function_token,
-                params,
-                body,
-            })
-        )
+        Ok(params)
     }
 
     pub(super) fn expression(&mut self) -> Result<Expr, ViskumError> {
-        self.logical()
+        self.assignment()
     }
 
-    pub(super) fn assignment(&mut self) -> Result<Expr, ViskumError> {
-        let expr = self.expression()?;
+    /// Assignments are right associative and evaluate to the assigned value,
+    /// so a = b = 0 sets both
+    fn assignment(&mut self) -> Result<Expr, ViskumError> {
+        let expr = self.pipeline()?;
 
         if
             self.match_tokens(
@@ -101,49 +470,106 @@ impl<'a> Parser<'a> {
                     TokenType::StarEqual,
                     TokenType::SlashEqual,
                     TokenType::PowerEqual,
-                    TokenType::Increment,
-                    TokenType::Decrement,
+                    TokenType::PercentEqual,
+                    TokenType::TildeSlashEqual,
+                    TokenType::AmpersandEqual,
+                    TokenType::PipeEqual,
+                    TokenType::ShiftLeftEqual,
+                    TokenType::ShiftRightEqual,
                 ]
             )?
         {
             let assignment_token = self.peek_previous()?;
-            if let Expr::Variable(var_expr) = expr.borrow() {
-                match self.peek_previous()?.ttype {
-                    TokenType::Increment | TokenType::Decrement => {
-                        return Ok(
-                            Expr::Assign(AssignExpr {
-                                token: var_expr.token.clone(),
-                                assignment_token: assignment_token,
-                                value: Box::from(expr),
-                            })
-                        );
-                    }
-                    _ => {
-                        let value = self.expression()?;
-
-                        return Ok(
-                            Expr::Assign(AssignExpr {
-                                token: var_expr.token.clone(),
-                                assignment_token: assignment_token,
-                                value: Box::from(value),
-                            })
-                        );
-                    }
-                }
-            } else {
+            let value = self.assignment()?;
+
+            return Ok(self.assignment_target(expr, assignment_token, value, false));
+        }
+
+        Ok(expr)
+    }
+
+    /// Turns the target of an assignment, '++' or '--' into the expression that
+    /// assigns to it. The value of '++' and '--' is the target itself, and
+    /// postfix ones evaluate to the value from before
+    fn assignment_target(
+        &mut self,
+        target: Expr,
+        assignment_token: Token,
+        value: Expr,
+        postfix: bool
+    ) -> Expr {
+        match target.borrow() {
+            Expr::Variable(var_expr) =>
+                Expr::Assign(AssignExpr {
+                    token: var_expr.token.clone(),
+                    assignment_token,
+                    value: Box::from(value),
+                    depth: Cell::new(None),
+                    postfix,
+                }),
+            Expr::Get(get_expr) =>
+                Expr::Set(SetExpr {
+                    object: get_expr.object.clone(),
+                    token: get_expr.token.clone(),
+                    assignment_token,
+                    value: Box::from(value),
+                    postfix,
+                }),
+            Expr::Index(index_expr) =>
+                Expr::IndexSet(IndexSetExpr {
+                    object: index_expr.object.clone(),
+                    bracket: index_expr.bracket.clone(),
+                    index: index_expr.index.clone(),
+                    assignment_token,
+                    value: Box::from(value),
+                    postfix,
+                }),
+            // (x) = 1 assigns to x
+            Expr::Grouping(grouping_expr) =>
+                self.assignment_target(
+                    (*grouping_expr.expression).clone(),
+                    assignment_token,
+                    value,
+                    postfix
+                ),
+            _ => {
                 report_error(
                     self.error_handler,
                     ViskumError::new(
-                        format!(
-                            "Invalid assignment target at '{}'",
-                            assignment_token.lexeme
-                        ).as_str(),
-                        assignment_token,
-                        "file.vs"
+                        format!("Invalid assignment target at '{}'", assignment_token.lexeme).as_str(),
+                        assignment_token
                     )
                 );
+
+                target
             }
         }
+    }
+
+    /// x |> f(y) is f(x, y), and x |> f is f(x). The value goes where the
+    /// placeholder is in x |> f(y, _), since the right side is then a
+    /// partially applied function
+    fn pipeline(&mut self) -> Result<Expr, ViskumError> {
+        let mut expr = self.logical()?;
+
+        while self.match_tokens(&[TokenType::PipeGreater])? {
+            let operator = self.peek_previous()?;
+            let function = self.logical()?;
+
+            expr = match function {
+                Expr::Call(mut call) => {
+                    call.arguments.insert(0, expr);
+                    Expr::Call(call)
+                }
+                function =>
+                    Expr::Call(CallExpr {
+                        callee: Box::from(function),
+                        paren: operator,
+                        arguments: vec![expr],
+                        named: Vec::new(),
+                    }),
+            };
+        }
 
         Ok(expr)
     }
@@ -153,7 +579,9 @@ impl<'a> Parser<'a> {
 
         if self.match_tokens(&[TokenType::Or, TokenType::And])? {
             let operator = self.peek_previous()?;
-            let rhs = self.expression()?;
+            // Not expression(), which would take a '|>' after it into the
+            // right operand
+            let rhs = self.logical()?;
 
             Ok(
                 Expr::Logical(LogicalExpr {
@@ -175,7 +603,7 @@ impl<'a> Parser<'a> {
 
             self.consume(TokenType::Colon, "Expected ':' in ternary expression")?;
 
-            let false_expr = self.expression()?;
+            let false_expr = self.ternary()?;
 
             return Ok(
                 Expr::Ternary(TernaryExpr {
@@ -205,7 +633,7 @@ impl<'a> Parser<'a> {
     }
 
     fn comparison(&mut self) -> Result<Expr, ViskumError> {
-        let mut expr = self.term()?;
+        let mut expr = self.range()?;
 
         while
             self.match_tokens(
@@ -214,11 +642,89 @@ impl<'a> Parser<'a> {
                     TokenType::GreaterEqual,
                     TokenType::Less,
                     TokenType::LessEqual,
+                    TokenType::In,
                 ]
             )?
         {
             let operator = self.peek_previous()?;
 
+            let right = self.range()?;
+            expr = Expr::Binary(BinaryExpr {
+                left: Box::from(expr),
+                operator: operator,
+                right: Box::from(right),
+            });
+        }
+
+        Ok(expr)
+    }
+
+    /// start..end or start..=end, optionally followed by step and the step
+    fn range(&mut self) -> Result<Expr, ViskumError> {
+        let expr = self.bitwise_or()?;
+
+        if !self.match_tokens(&[TokenType::DotDot, TokenType::DotDotEqual])? {
+            return Ok(expr);
+        }
+
+        let operator = self.peek_previous()?;
+        let end = self.bitwise_or()?;
+
+        // step is only a keyword right after a range
+        let step = if self.check(&TokenType::Identifier)? && self.peek()?.lexeme == "step" {
+            self.advance()?;
+            Some(Box::from(self.bitwise_or()?))
+        } else {
+            None
+        };
+
+        Ok(
+            Expr::Range(RangeExpr {
+                start: Box::from(expr),
+                operator,
+                end: Box::from(end),
+                step,
+            })
+        )
+    }
+
+    fn bitwise_or(&mut self) -> Result<Expr, ViskumError> {
+        let mut expr = self.bitwise_and()?;
+
+        while self.match_tokens(&[TokenType::Pipe])? {
+            let operator = self.peek_previous()?;
+            let right = self.bitwise_and()?;
+            expr = Expr::Binary(BinaryExpr {
+                left: Box::from(expr),
+                operator: operator,
+                right: Box::from(right),
+            });
+        }
+
+        Ok(expr)
+    }
+
+    fn bitwise_and(&mut self) -> Result<Expr, ViskumError> {
+        let mut expr = self.shift()?;
+
+        while self.match_tokens(&[TokenType::Ampersand])? {
+            let operator = self.peek_previous()?;
+            let right = self.shift()?;
+            expr = Expr::Binary(BinaryExpr {
+                left: Box::from(expr),
+                operator: operator,
+                right: Box::from(right),
+            });
+        }
+
+        Ok(expr)
+    }
+
+    fn shift(&mut self) -> Result<Expr, ViskumError> {
+        let mut expr = self.term()?;
+
+        while self.match_tokens(&[TokenType::ShiftLeft, TokenType::ShiftRight])? {
+            let operator = self.peek_previous()?;
             let right = self.term()?;
             expr = Expr::Binary(BinaryExpr {
                 left: Box::from(expr),
@@ -249,7 +755,11 @@ impl<'a> Parser<'a> {
     fn factor(&mut self) -> Result<Expr, ViskumError> {
         let mut expr = self.unary()?;
 
-        while self.match_tokens(&[TokenType::Slash, TokenType::Star, TokenType::Power])? {
+        while
+            self.match_tokens(
+                &[TokenType::Slash, TokenType::Star, TokenType::Percent, TokenType::TildeSlash]
+            )?
+        {
             let operator = self.peek_previous()?;
             let right = self.unary()?;
             expr = Expr::Binary(BinaryExpr {
@@ -273,14 +783,43 @@ impl<'a> Parser<'a> {
             return Ok(Expr::Postfix(PostfixExpr { left: Box::from(left), operator: operator }));
         }
 
+        // Prefix e.g. ++x, which evaluates to the new value
+        if self.match_tokens(&[TokenType::Increment, TokenType::Decrement])? {
+            let operator = self.peek_previous()?;
+            let target = self.unary()?;
+
+            return Ok(self.assignment_target(target.clone(), operator, target, false));
+        }
+
         // Prefix e.g. !5
-        if self.match_tokens(&[TokenType::Bang, TokenType::Minus])? {
+        if self.match_tokens(&[TokenType::Bang, TokenType::Minus, TokenType::Tilde])? {
             let operator = self.peek_previous()?;
             let right = self.unary()?;
             return Ok(Expr::Prefix(PrefixExpr { operator: operator, right: Box::from(right) }));
         }
 
-        Ok(self.call()?)
+        self.power()
+    }
+
+    // Power binds tighter than a prefix on its left and is right associative,
+    // so -2^2 is -(2^2) and 2^3^2 is 2^(3^2)
+    fn power(&mut self) -> Result<Expr, ViskumError> {
+        let expr = self.call()?;
+
+        if self.match_tokens(&[TokenType::Power])? {
+            let operator = self.peek_previous()?;
+            let right = self.unary()?;
+
+            return Ok(
+                Expr::Binary(BinaryExpr {
+                    left: Box::from(expr),
+                    operator: operator,
+                    right: Box::from(right),
+                })
+            );
+        }
+
+        Ok(expr)
     }
 
     fn call(&mut self) -> Result<Expr, ViskumError> {
@@ -289,6 +828,28 @@ impl<'a> Parser<'a> {
         loop {
             if self.match_tokens(&[TokenType::LeftParen])? {
                 expr = self.finish_call(expr)?;
+            } else if self.match_tokens(&[TokenType::Dot])? {
+                let token = self.consume_and_get(
+                    TokenType::Identifier,
+                    "Expected property name after '.'"
+                )?;
+                expr = Expr::Get(GetExpr { object: Box::from(expr), token: token });
+            } else if self.match_tokens(&[TokenType::LeftBracket])? {
+                let bracket = self.peek_previous()?;
+                let index = self.expression()?;
+                self.consume(TokenType::RightBracket, "Expected ']' after index")?;
+
+                expr = Expr::Index(IndexExpr {
+                    object: Box::from(expr),
+                    bracket: bracket,
+                    index: Box::from(index),
+                });
+            } else if self.match_tokens(&[TokenType::Factorial])? {
+                let operator = self.peek_previous()?;
+                expr = Expr::Postfix(PostfixExpr { left: Box::from(expr), operator: operator });
+            } else if self.match_tokens(&[TokenType::Increment, TokenType::Decrement])? {
+                let operator = self.peek_previous()?;
+                expr = self.assignment_target(expr.clone(), operator, expr, true);
             } else {
                 break;
             }
@@ -297,23 +858,87 @@ impl<'a> Parser<'a> {
         Ok(expr)
     }
 
+    /// A call with a '_' placeholder for an argument, like add(_, 1), gives a
+    /// function that takes the missing arguments
     fn finish_call(&mut self, calle: Expr) -> Result<Expr, ViskumError> {
+        let (arguments, named, paren) = self.arguments()?;
+
+        if arguments.iter().any(|argument| matches!(argument, Expr::Placeholder(_))) {
+            return Ok(
+                Expr::Partial(PartialExpr {
+                    callee: Box::from(calle),
+                    paren,
+                    arguments,
+                    named,
+                })
+            );
+        }
+
+        Ok(
+            Expr::Call(CallExpr {
+                callee: Box::from(calle),
+                paren: paren,
+                arguments: arguments,
+                named,
+            })
+        )
+    }
+
+    /// Positional arguments, followed by named ones like greeting: "hey"
+    fn arguments(&mut self) -> Result<(Vec<Expr>, Vec<NamedArgument>, Token), ViskumError> {
         let mut arguments: Vec<Expr> = Vec::new();
+        let mut named: Vec<NamedArgument> = Vec::new();
 
         if !self.check(&TokenType::RightParen)? {
-            arguments.push(self.expression()?);
-            while self.match_tokens(&[TokenType::Comma])? {
-                if arguments.len() >= 255 {
+            loop {
+                if arguments.len() + named.len() >= 255 {
                     report_error(
                         self.error_handler,
                         ViskumError::new(
                             "Cannot have more than 255 arguments",
-                            self.peek_previous()?,
-                            "file.vs"
+                            self.peek_previous()?
                         )
                     );
                 }
-                arguments.push(self.expression()?);
+
+                if self.check(&TokenType::Identifier)? && self.check_next(&TokenType::Colon)? {
+                    let token = self.consume_and_get(TokenType::Identifier, "Expected parameter name")?;
+                    self.advance()?;
+
+                    if named.iter().any(|argument| argument.token.lexeme == token.lexeme) {
+                        report_error(
+                            self.error_handler,
+                            ViskumError::new(
+                                format!("Argument '{}' is given more than once", token.lexeme).as_str(),
+                                token.clone()
+                            )
+                        );
+                    }
+
+                    named.push(NamedArgument { token, value: self.expression()? });
+                } else if self.check_placeholder()? {
+                    let token = self.peek()?;
+                    self.advance()?;
+                    arguments.push(Expr::Placeholder(PlaceholderExpr { token }));
+                } else {
+                    let argument = self.expression()?;
+
+                    if let Some(previous) = named.last() {
+                        report_error(
+                            self.error_handler,
+                            ViskumError::new(
+                                "Positional arguments can't come after named arguments",
+                                previous.token.clone()
+                            )
+                        );
+                    }
+
+                    arguments.push(argument);
+                }
+
+                if !self.match_tokens(&[TokenType::Comma])? {
+                    break;
+                }
             }
         }
 
@@ -322,12 +947,17 @@ impl<'a> Parser<'a> {
             "Expected ')' after function arguments"
         )?;
 
+        Ok((arguments, named, paren))
+    }
+
+    /// Whether the next argument is a '_' standing in for a missing one
+    fn check_placeholder(&self) -> Result<bool, ViskumError> {
+        let token = self.peek()?;
+
         Ok(
-            Expr::Call(CallExpr {
-                callee: Box::from(calle),
-                paren: paren,
-                arguments: arguments,
-            })
+            token.is(TokenType::Identifier) &&
+                token.lexeme == "_" &&
+                (self.check_next(&TokenType::Comma)? || self.check_next(&TokenType::RightParen)?)
         )
     }
 
@@ -350,12 +980,98 @@ impl<'a> Parser<'a> {
             );
         }
 
+        if self.match_tokens(&[TokenType::Interpolation])? {
+            return self.interpolation();
+        }
+
         if self.match_tokens(&[TokenType::Identifier])? {
-            return Ok(Expr::Variable(VariableExpr { token: self.peek_previous()? }));
+            return Ok(
+                Expr::Variable(VariableExpr {
+                    token: self.peek_previous()?,
+                    depth: Cell::new(None),
+                })
+            );
+        }
+
+        if self.match_tokens(&[TokenType::LeftBracket])? {
+            return self.collection();
+        }
+
+        if self.match_tokens(&[TokenType::Fn])? {
+            return self.lambda();
+        }
+
+        if self.match_tokens(&[TokenType::Match])? {
+            return self.match_expression();
+        }
+
+        if self.match_tokens(&[TokenType::If])? {
+            return self.if_expression();
+        }
+
+        if self.match_tokens(&[TokenType::Loop])? {
+            return self.loop_expression(None);
+        }
+
+        if self.match_tokens(&[TokenType::Label])? {
+            let label = self.peek_previous()?;
+            self.consume(TokenType::Colon, "Expected ':' after loop label")?;
+            self.consume(TokenType::Loop, "Expected 'loop' after label, since only loop has a value")?;
+
+            return self.loop_expression(Some(label));
+        }
+
+        if self.match_tokens(&[TokenType::LeftBrace])? {
+            return self.block_expression();
+        }
+
+        if self.match_tokens(&[TokenType::This])? {
+            return Ok(
+                Expr::This(ThisExpr {
+                    keyword: self.peek_previous()?,
+                    depth: Cell::new(None),
+                })
+            );
+        }
+
+        if self.match_tokens(&[TokenType::New])? {
+            let keyword = self.peek_previous()?;
+            let mut class = self.primary()?;
+
+            while self.match_tokens(&[TokenType::Dot])? {
+                let token = self.consume_and_get(
+                    TokenType::Identifier,
+                    "Expected property name after '.'"
+                )?;
+                class = Expr::Get(GetExpr { object: Box::from(class), token: token });
+            }
+
+            self.consume(TokenType::LeftParen, "Expected '(' after class name")?;
+            let (arguments, named, paren) = self.arguments()?;
+
+            return Ok(
+                Expr::New(NewExpr {
+                    keyword: keyword,
+                    class: Box::from(class),
+                    paren: paren,
+                    arguments: arguments,
+                    named,
+                })
+            );
         }
 
         if self.match_tokens(&[TokenType::LeftParen])? {
+            if self.match_tokens(&[TokenType::RightParen])? {
+                return Ok(Expr::Tuple(TupleExpr { elements: Vec::new() }));
+            }
+
             let expr = self.expression()?;
+
+            // A comma makes it a tuple, so (x,) is a tuple of one
+            if self.match_tokens(&[TokenType::Comma])? {
+                return self.tuple(expr);
+            }
+
             self.consume(TokenType::RightParen, "Expected ')' after expression")?;
 
             if self.match_tokens(&[TokenType::Factorial])? {
@@ -377,9 +1093,77 @@ impl<'a> Parser<'a> {
         Err(
             ViskumError::new(
                 format!("Expected expression: Unexpected '{}'", self.peek()?.lexeme).as_str(),
-                self.peek()?,
-                "file.vs"
+                self.peek()?
             )
         )
     }
+
+    fn collection(&mut self) -> Result<Expr, ViskumError> {
+        let bracket = self.peek_previous()?;
+
+        // [:] is the empty map
+        if self.match_tokens(&[TokenType::Colon])? {
+            self.consume(TokenType::RightBracket, "Expected ']' after ':' in empty map")?;
+
+            return Ok(Expr::Map(MapExpr { bracket: bracket, keys: Vec::new(), values: Vec::new() }));
+        }
+
+        let mut elements: Vec<Expr> = Vec::new();
+
+        if !self.check(&TokenType::RightBracket)? {
+            let first = self.expression()?;
+
+            if self.match_tokens(&[TokenType::Colon])? {
+                return self.map(bracket, first);
+            }
+
+            elements.push(first);
+
+            while self.match_tokens(&[TokenType::Comma])? {
+                if self.check(&TokenType::RightBracket)? {
+                    break;
+                }
+                elements.push(self.expression()?);
+            }
+        }
+
+        self.consume(TokenType::RightBracket, "Expected ']' after list elements")?;
+
+        Ok(Expr::List(ListExpr { elements: elements }))
+    }
+
+    fn tuple(&mut self, first: Expr) -> Result<Expr, ViskumError> {
+        let mut elements = vec![first];
+
+        while !self.check(&TokenType::RightParen)? {
+            elements.push(self.expression()?);
+
+            if !self.match_tokens(&[TokenType::Comma])? {
+                break;
+            }
+        }
+
+        self.consume(TokenType::RightParen, "Expected ')' after tuple elements")?;
+
+        Ok(Expr::Tuple(TupleExpr { elements }))
+    }
+
+    fn map(&mut self, bracket: Token, first_key: Expr) -> Result<Expr, ViskumError> {
+        let mut keys: Vec<Expr> = vec![first_key];
+        let mut values: Vec<Expr> = vec![self.expression()?];
+
+        while self.match_tokens(&[TokenType::Comma])? {
+            if self.check(&TokenType::RightBracket)? {
+                break;
+            }
+
+            keys.push(self.expression()?);
+            self.consume(TokenType::Colon, "Expected ':' after map key")?;
+            values.push(self.expression()?);
+        }
+
+        self.consume(TokenType::RightBracket, "Expected ']' after map entries")?;
+
+        Ok(Expr::Map(MapExpr { bracket: bracket, keys: keys, values: values }))
+    }
 }
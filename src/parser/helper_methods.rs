@@ -14,7 +14,7 @@ impl<'a> Parser<'a> {
             Ok(token)
         } else {
             let token = self.peek()?;
-            Err(ViskumError::new(msg, token, "file.vs"))
+            Err(ViskumError::new(msg, token))
         }
     }
 
@@ -24,10 +24,20 @@ impl<'a> Parser<'a> {
         } else {
             let token = self.peek()?;
             let new_msg = format!("{}: Unexpected '{}'", msg, token.lexeme);
-            Err(ViskumError::new(new_msg.as_str(), token, "file.vs"))
+            Err(ViskumError::new(new_msg.as_str(), token))
         }
     }
 
+    /// Statements end with a ';' or a newline, which the lexer turns into a ';'.
+    /// The last statement in a block or a file doesn't need either
+    pub(super) fn consume_end_of_statement(&mut self, msg: &str) -> Result<(), ViskumError> {
+        if self.check(&TokenType::RightBrace)? || self.is_at_end()? {
+            return Ok(());
+        }
+
+        self.consume(TokenType::Semicolon, msg)
+    }
+
     pub(super) fn match_tokens(&mut self, ttypes: &[TokenType]) -> Result<bool, ViskumError> {
         for ttype in ttypes {
             if self.check(ttype)? {
@@ -57,7 +67,7 @@ impl<'a> Parser<'a> {
             Ok(true)
         } else {
             let new_msg = format!("{}: Unexpected '{}'", msg, token.lexeme);
-            Err(ViskumError::new(new_msg.as_str(), token, "file.vs"))
+            Err(ViskumError::new(new_msg.as_str(), token))
         }
     }
 
@@ -86,8 +96,7 @@ impl<'a> Parser<'a> {
                 Err(
                     ViskumError::new(
                         "Internal error: Failed to find token",
-                        Token::invalid(None),
-                        "file.vs"
+                        Token::invalid(None)
                     )
                 ),
         }
@@ -100,8 +109,7 @@ impl<'a> Parser<'a> {
                 Err(
                     ViskumError::new(
                         "Internal error: Failed to find token",
-                        Token::invalid(None),
-                        "file.vs"
+                        Token::invalid(None)
                     )
                 ),
         }
@@ -114,8 +122,7 @@ impl<'a> Parser<'a> {
                 Err(
                     ViskumError::new(
                         "Internal error: Failed to find token",
-                        Token::invalid(None),
-                        "file.vs"
+                        Token::invalid(None)
                     )
                 ),
         }
@@ -132,12 +139,17 @@ impl<'a> Parser<'a> {
                 matches!(
                     self.peek()?.ttype,
                     TokenType::Class |
+                        TokenType::Enum |
+                        TokenType::Import |
+                        TokenType::Throw |
+                        TokenType::Try |
                         TokenType::Let |
                         TokenType::For |
                         TokenType::If |
                         TokenType::While |
                         TokenType::Print |
-                        TokenType::Return
+                        TokenType::Return |
+                        TokenType::Yield
                 )
             {
                 return Ok(());
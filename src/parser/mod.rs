@@ -1,30 +1,37 @@
-use std::cell::RefCell;
+use std::{ cell::RefCell, rc::Rc };
 
 mod helper_methods;
 mod expression_methods;
 mod statements;
+mod patterns;
 
 use crate::{
     token::{ Token, TokenType },
     error_handler::{ ErrorHandler, ViskumError },
-    stmt::{ Stmt, BlockStmt, LoopControlStmt },
+    stmt::{ Stmt, BlockStmt },
 };
 
 pub struct Parser<'a> {
     tokens: &'a Vec<Token>,
     current: usize,
     error_handler: &'a RefCell<ErrorHandler>,
+    // Whether the body of the function being parsed contains a yield
+    function_yields: bool,
 }
 
 impl<'a> Parser<'a> {
     pub fn new(tokens: &'a Vec<Token>, error_handler: &'a RefCell<ErrorHandler>) -> Self {
-        Parser { tokens: tokens, current: 0, error_handler: error_handler }
+        Parser { tokens: tokens, current: 0, error_handler: error_handler, function_yields: false }
     }
 
     pub fn parse(&mut self) -> Result<Vec<Stmt>, ViskumError> {
         let mut statements: Vec<Stmt> = Vec::new();
 
         while !self.is_at_end()? {
+            // Empty statements, like the end of the line after a block
+            if self.match_tokens(&[TokenType::Semicolon])? {
+                continue;
+            }
             statements.push(self.declaration()?);
         }
 
@@ -35,8 +42,15 @@ impl<'a> Parser<'a> {
     fn declaration(&mut self) -> Result<Stmt, ViskumError> {
         let result = if self.match_tokens(&[TokenType::Let])? {
             self.variable_declaration()
-        } else if self.match_tokens(&[TokenType::Fn])? {
+        } else if self.check(&TokenType::Fn)? && self.check_next(&TokenType::Identifier)? {
+            self.advance()?;
             self.function_declaration("function".to_string())
+        } else if self.match_tokens(&[TokenType::Class])? {
+            self.class_declaration()
+        } else if self.match_tokens(&[TokenType::Enum])? {
+            self.enum_declaration()
+        } else if self.match_tokens(&[TokenType::Import])? {
+            self.import_declaration()
         } else {
             self.statement()
         };
@@ -51,20 +65,30 @@ impl<'a> Parser<'a> {
     fn statement(&mut self) -> Result<Stmt, ViskumError> {
         if self.match_tokens(&[TokenType::Return])? {
             self.return_statement()
+        } else if self.match_tokens(&[TokenType::Yield])? {
+            self.yield_statement()
+        } else if self.check(&TokenType::Label)? && self.check_next(&TokenType::Colon)? {
+            self.labeled_loop()
         } else if self.match_tokens(&[TokenType::While])? {
-            self.while_statement()
+            self.while_statement(None)
         } else if self.match_tokens(&[TokenType::Loop])? {
-            self.loop_statement()
+            self.loop_statement(None)
+        } else if self.match_tokens(&[TokenType::For])? {
+            self.for_statement(None)
         } else if self.match_tokens(&[TokenType::Break, TokenType::Continue])? {
-            let keyword = self.peek_previous()?;
-            self.consume(TokenType::Semicolon, "Expected ';' after loop control statement")?;
-            Ok(Stmt::LoopControl(LoopControlStmt { keyword: keyword }))
+            self.loop_control_statement()
         } else if self.match_tokens(&[TokenType::If])? {
             self.if_statement()
         } else if self.match_tokens(&[TokenType::Print])? {
             self.print_statement()
         } else if self.match_tokens(&[TokenType::LeftBrace])? {
-            Ok(Stmt::Block(BlockStmt { statements: self.block()? }))
+            Ok(Stmt::Block(BlockStmt { statements: Rc::from(self.block()?) }))
+        } else if self.match_tokens(&[TokenType::Match])? {
+            self.match_statement()
+        } else if self.match_tokens(&[TokenType::Throw])? {
+            self.throw_statement()
+        } else if self.match_tokens(&[TokenType::Try])? {
+            self.try_statement()
         } else {
             self.expression_statement()
         }
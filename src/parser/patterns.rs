@@ -0,0 +1,235 @@
+use std::cell::Cell;
+
+use crate::{
+    expr::{ Expr, MatchExpr, VariableExpr, GetExpr },
+    token::{ TokenType, Literal },
+    error_handler::ViskumError,
+    pattern::{ Pattern, MatchArm },
+    util::report_error,
+};
+
+use super::Parser;
+
+impl<'a> Parser<'a> {
+    pub(super) fn match_expression(&mut self) -> Result<Expr, ViskumError> {
+        let keyword = self.peek_previous()?;
+        let subject = self.expression()?;
+
+        self.consume(TokenType::LeftBrace, "Expected '{' after match value")?;
+
+        let mut arms = Vec::new();
+
+        while !self.check(&TokenType::RightBrace)? && !self.is_at_end()? {
+            arms.push(self.match_arm()?);
+        }
+
+        self.consume(TokenType::RightBrace, "Expected '}' after match arms")?;
+
+        Ok(Expr::Match(MatchExpr { keyword, subject: Box::from(subject), arms }))
+    }
+
+    fn match_arm(&mut self) -> Result<MatchArm, ViskumError> {
+        let pattern = self.pattern()?;
+
+        let guard = if self.match_tokens(&[TokenType::If])? {
+            Some(self.expression()?)
+        } else {
+            None
+        };
+
+        self.consume(TokenType::FatArrow, "Expected '=>' after pattern")?;
+
+        let body = if self.match_tokens(&[TokenType::LeftBrace])? {
+            let block = self.block_expression()?;
+            self.match_tokens(&[TokenType::Comma, TokenType::Semicolon])?;
+
+            block
+        } else {
+            let expr = self.expression()?;
+            // Arms are separated by commas or newlines
+            if
+                !self.check(&TokenType::RightBrace)? &&
+                !self.match_tokens(&[TokenType::Comma, TokenType::Semicolon])?
+            {
+                let token = self.peek()?;
+                return Err(
+                    ViskumError::new(
+                        format!("Expected ',' after match arm: Unexpected '{}'", token.lexeme).as_str(),
+                        token
+                    )
+                );
+            }
+
+            expr
+        };
+
+        Ok(MatchArm { pattern, guard, body })
+    }
+
+    fn pattern(&mut self) -> Result<Pattern, ViskumError> {
+        let first = self.pattern_alternative()?;
+
+        if !self.check(&TokenType::Pipe)? {
+            return Ok(first);
+        }
+
+        let mut alternatives = vec![first];
+        while self.match_tokens(&[TokenType::Pipe])? {
+            alternatives.push(self.pattern_alternative()?);
+        }
+
+        // Only some of the alternatives would define the name
+        let binding = alternatives.iter().find_map(|alternative| alternative.bindings().first().copied());
+        if let Some(binding) = binding {
+            report_error(
+                self.error_handler,
+                ViskumError::new(
+                    format!(
+                        "Cannot bind '{}' inside a pattern with '|'",
+                        binding.lexeme
+                    ).as_str(),
+                    binding.clone()
+                )
+            );
+        }
+
+        Ok(Pattern::Or(alternatives))
+    }
+
+    fn pattern_alternative(&mut self) -> Result<Pattern, ViskumError> {
+        if self.check(&TokenType::Identifier)? && self.check_next(&TokenType::Dot)? {
+            return self.variant_pattern();
+        }
+
+        if self.match_tokens(&[TokenType::LeftParen])? {
+            return self.tuple_pattern();
+        }
+
+        if self.match_tokens(&[TokenType::Identifier])? {
+            let token = self.peek_previous()?;
+
+            if token.lexeme == "_" {
+                return Ok(Pattern::Wildcard);
+            }
+            return Ok(Pattern::Binding(token));
+        }
+
+        let start = self.pattern_literal()?;
+
+        if self.match_tokens(&[TokenType::DotDot, TokenType::DotDotEqual])? {
+            let token = self.peek_previous()?;
+            let end = self.pattern_literal()?;
+
+            return Ok(Pattern::Range {
+                start,
+                end,
+                inclusive: token.is(TokenType::DotDotEqual),
+                token,
+            });
+        }
+
+        Ok(Pattern::Literal(start))
+    }
+
+    /// Parses patterns like Shape.Circle(r), where the part before the last '.'
+    /// evaluates to the enum
+    fn variant_pattern(&mut self) -> Result<Pattern, ViskumError> {
+        let mut enum_expr = Expr::Variable(VariableExpr {
+            token: self.consume_and_get(TokenType::Identifier, "Expected enum name")?,
+            depth: Cell::new(None),
+        });
+
+        self.consume(TokenType::Dot, "Expected '.' after enum name")?;
+        let mut tag = self.consume_and_get(TokenType::Identifier, "Expected variant name")?;
+
+        while self.match_tokens(&[TokenType::Dot])? {
+            enum_expr = Expr::Get(GetExpr { object: Box::from(enum_expr), token: tag });
+            tag = self.consume_and_get(TokenType::Identifier, "Expected variant name")?;
+        }
+
+        let fields = if self.match_tokens(&[TokenType::LeftParen])? {
+            let mut fields = Vec::new();
+
+            if !self.check(&TokenType::RightParen)? {
+                loop {
+                    fields.push(self.pattern()?);
+
+                    if !self.match_tokens(&[TokenType::Comma])? {
+                        break;
+                    }
+                }
+            }
+            self.consume(TokenType::RightParen, "Expected ')' after variant fields")?;
+
+            Some(fields)
+        } else {
+            None
+        };
+
+        Ok(Pattern::Variant { enum_expr, tag, fields })
+    }
+
+    /// Parses (a, b). Like in expressions, (p) is just p and (p,) is a tuple of one
+    fn tuple_pattern(&mut self) -> Result<Pattern, ViskumError> {
+        let mut elements = Vec::new();
+        let mut is_tuple = true;
+
+        if !self.check(&TokenType::RightParen)? {
+            elements.push(self.pattern()?);
+            is_tuple = self.check(&TokenType::Comma)?;
+
+            while self.match_tokens(&[TokenType::Comma])? {
+                if self.check(&TokenType::RightParen)? {
+                    break;
+                }
+                elements.push(self.pattern()?);
+            }
+        }
+
+        self.consume(TokenType::RightParen, "Expected ')' after tuple pattern")?;
+
+        if !is_tuple {
+            return Ok(elements.remove(0));
+        }
+
+        Ok(Pattern::Tuple(elements))
+    }
+
+    fn pattern_literal(&mut self) -> Result<Literal, ViskumError> {
+        if self.match_tokens(&[TokenType::True])? {
+            return Ok(Literal::Bool(true));
+        }
+        if self.match_tokens(&[TokenType::False])? {
+            return Ok(Literal::Bool(false));
+        }
+        if self.match_tokens(&[TokenType::Null])? {
+            return Ok(Literal::Null);
+        }
+        if self.match_tokens(&[TokenType::Number, TokenType::String])? {
+            return Ok(self.peek_previous()?.literal.unwrap_or(Literal::Null));
+        }
+
+        if self.match_tokens(&[TokenType::Minus])? {
+            let number = self.consume_and_get(TokenType::Number, "Expected number after '-'")?;
+
+            match number.literal {
+                Some(Literal::Int(x)) => {
+                    return Ok(Literal::Int(-x));
+                }
+                Some(Literal::Num(x)) => {
+                    return Ok(Literal::Num(-x));
+                }
+                _ => (),
+            }
+        }
+
+        let token = self.peek()?;
+
+        Err(
+            ViskumError::new(
+                format!("Expected pattern: Unexpected '{}'", token.lexeme).as_str(),
+                token
+            )
+        )
+    }
+}
@@ -1,8 +1,11 @@
+use std::{ path::Path, rc::Rc };
+
 use crate::{
     stmt::*,
-    token::{ TokenType, Literal },
+    token::{ TokenType, Literal, Token },
+    util::{ report_error, is_identifier },
     error_handler::ViskumError,
-    expr::{ Expr, LiteralExpr },
+    expr::{ Expr, LiteralExpr, TupleExpr },
 };
 
 use super::Parser;
@@ -11,19 +14,78 @@ impl<'a> Parser<'a> {
     pub(super) fn print_statement(&mut self) -> Result<Stmt, ViskumError> {
         let value = self.expression()?;
 
-        self.consume(TokenType::Semicolon, "Epxected ';' after expression")?;
+        self.consume_end_of_statement("Epxected ';' after expression")?;
 
         Ok(Stmt::Print(PrintStmt { expression: value }))
     }
 
     pub(super) fn expression_statement(&mut self) -> Result<Stmt, ViskumError> {
-        let expr = self.assignment()?;
+        let expr = self.expression()?;
 
-        self.consume(TokenType::Semicolon, "Expected ';' after expression")?;
+        self.consume_end_of_statement("Expected ';' after expression")?;
 
         Ok(Stmt::Expression(ExpressionStmt { expression: expr }))
     }
 
+    /// A match used as a statement ends with '}', so the ';' is optional
+    pub(super) fn match_statement(&mut self) -> Result<Stmt, ViskumError> {
+        let expr = self.match_expression()?;
+
+        self.match_tokens(&[TokenType::Semicolon])?;
+
+        Ok(Stmt::Expression(ExpressionStmt { expression: expr }))
+    }
+
+    /// import "path/to/util.vs" [as name]; or import util [as name];
+    pub(super) fn import_declaration(&mut self) -> Result<Stmt, ViskumError> {
+        let keyword = self.peek_previous()?;
+
+        let path_token = if self.match_tokens(&[TokenType::String, TokenType::Identifier])? {
+            self.peek_previous()?
+        } else {
+            let token = self.peek()?;
+            return Err(
+                ViskumError::new(
+                    format!("Expected module path after 'import': Unexpected '{}'", token.lexeme).as_str(),
+                    token
+                )
+            );
+        };
+
+        let path = match &path_token.literal {
+            Some(Literal::Str(path)) => path.clone(),
+            _ => format!("{}.vs", path_token.lexeme),
+        };
+
+        let name = if self.match_tokens(&[TokenType::As])? {
+            self.consume_and_get(TokenType::Identifier, "Expected module name after 'as'")?
+        } else {
+            let stem = Path::new(&path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            if !is_identifier(&stem) {
+                report_error(
+                    self.error_handler,
+                    ViskumError::new(
+                        format!(
+                            "'{}' can't be used as a module name. Name the module with 'as'",
+                            stem
+                        ).as_str(),
+                        path_token.clone()
+                    )
+                );
+            }
+
+            Token { ttype: TokenType::Identifier, lexeme: stem, literal: None, ..path_token }
+        };
+
+        self.consume_end_of_statement("Expected ';' after import")?;
+
+        Ok(Stmt::Import(ImportStmt { keyword, path, name }))
+    }
+
     pub(super) fn if_statement(&mut self) -> Result<Stmt, ViskumError> {
         let condition = self.expression()?;
 
@@ -39,9 +101,9 @@ impl<'a> Parser<'a> {
         Ok(
             Stmt::If(IfStmt {
                 condition: condition,
-                then_branch: Box::from(then_branch),
+                then_branch: Rc::from(then_branch),
                 else_branch: if let Some(else_branch) = else_branch {
-                    Some(Box::from(else_branch))
+                    Some(Rc::from(else_branch))
                 } else {
                     None
                 },
@@ -49,27 +111,135 @@ impl<'a> Parser<'a> {
         )
     }
 
-    pub(super) fn while_statement(&mut self) -> Result<Stmt, ViskumError> {
+    /// 'label: followed by the loop it names
+    pub(super) fn labeled_loop(&mut self) -> Result<Stmt, ViskumError> {
+        let label = self.consume_and_get(TokenType::Label, "Expected loop label")?;
+        self.consume(TokenType::Colon, "Expected ':' after loop label")?;
+
+        if self.match_tokens(&[TokenType::While])? {
+            self.while_statement(Some(label))
+        } else if self.match_tokens(&[TokenType::Loop])? {
+            self.loop_statement(Some(label))
+        } else if self.match_tokens(&[TokenType::For])? {
+            self.for_statement(Some(label))
+        } else {
+            let token = self.peek()?;
+            Err(
+                ViskumError::new(
+                    format!(
+                        "Expected a loop after label {}: Unexpected '{}'",
+                        label.lexeme,
+                        token.lexeme
+                    ).as_str(),
+                    token
+                )
+            )
+        }
+    }
+
+    pub(super) fn while_statement(&mut self, label: Option<Token>) -> Result<Stmt, ViskumError> {
         let condition = self.expression()?;
         self.ensure(TokenType::LeftBrace, "Expected '{' after condition")?;
 
         let body = self.statement()?;
 
-        Ok(Stmt::While(WhileStmt { condition: condition, body: Box::from(body) }))
+        Ok(Stmt::While(WhileStmt { label, condition: condition, body: Rc::from(body) }))
+    }
+
+    /// A loop used as a statement, whose value is thrown away
+    pub(super) fn loop_statement(&mut self, label: Option<Token>) -> Result<Stmt, ViskumError> {
+        let expr = self.loop_expression(label)?;
+
+        self.match_tokens(&[TokenType::Semicolon])?;
+
+        Ok(Stmt::Expression(ExpressionStmt { expression: expr }))
+    }
+
+    /// break ['label] [value]; or continue ['label];
+    pub(super) fn loop_control_statement(&mut self) -> Result<Stmt, ViskumError> {
+        let keyword = self.peek_previous()?;
+
+        let label = if self.match_tokens(&[TokenType::Label])? {
+            Some(self.peek_previous()?)
+        } else {
+            None
+        };
+
+        let value = if
+            keyword.is(TokenType::Break) &&
+            !self.check(&TokenType::Semicolon)? &&
+            !self.check(&TokenType::RightBrace)? &&
+            !self.is_at_end()?
+        {
+            Some(self.expression()?)
+        } else {
+            None
+        };
+
+        self.consume_end_of_statement("Expected ';' after loop control statement")?;
+
+        Ok(Stmt::LoopControl(LoopControlStmt { keyword, label, value }))
+    }
+
+    pub(super) fn for_statement(&mut self, label: Option<Token>) -> Result<Stmt, ViskumError> {
+        if self.check(&TokenType::Identifier)? && self.check_next(&TokenType::In)? {
+            return self.for_in_statement(label);
+        }
+
+        let initializer = if self.match_tokens(&[TokenType::Semicolon])? {
+            None
+        } else if self.match_tokens(&[TokenType::Let])? {
+            Some(self.variable_declaration()?)
+        } else {
+            Some(self.expression_statement()?)
+        };
+
+        let condition = if !self.check(&TokenType::Semicolon)? {
+            Some(self.expression()?)
+        } else {
+            None
+        };
+        self.consume(TokenType::Semicolon, "Expected ';' after loop condition")?;
+
+        let increment = if !self.check(&TokenType::LeftBrace)? {
+            Some(self.expression()?)
+        } else {
+            None
+        };
+        self.ensure(TokenType::LeftBrace, "Expected '{' after for clauses")?;
+
+        let body = self.statement()?;
+
+        Ok(
+            Stmt::For(ForStmt {
+                label,
+                initializer: initializer.map(Box::from),
+                condition: condition,
+                increment: increment,
+                body: Rc::from(body),
+            })
+        )
     }
 
-    pub(super) fn loop_statement(&mut self) -> Result<Stmt, ViskumError> {
-        self.ensure(TokenType::LeftBrace, "Expected '{' after loop keyword")?;
+    fn for_in_statement(&mut self, label: Option<Token>) -> Result<Stmt, ViskumError> {
+        let token = self.consume_and_get(TokenType::Identifier, "Expected loop variable name")?;
+        self.consume(TokenType::In, "Expected 'in' after loop variable")?;
+
+        let iterable = self.expression()?;
+        self.ensure(TokenType::LeftBrace, "Expected '{' after iterable")?;
 
         let body = self.statement()?;
 
-        Ok(Stmt::Loop(LoopStmt { body: Box::from(body) }))
+        Ok(Stmt::ForIn(ForInStmt { label, token: token, iterable: iterable, body: Rc::from(body) }))
     }
 
     pub(super) fn block(&mut self) -> Result<Vec<Stmt>, ViskumError> {
         let mut statements: Vec<Stmt> = Vec::new();
 
         while !self.check(&TokenType::RightBrace)? && !self.is_at_end()? {
+            if self.match_tokens(&[TokenType::Semicolon])? {
+                continue;
+            }
             statements.push(self.declaration()?);
         }
 
@@ -80,14 +250,96 @@ impl<'a> Parser<'a> {
 
     pub(super) fn return_statement(&mut self) -> Result<Stmt, ViskumError> {
         let keyword = self.peek_previous()?;
-        let value = if !self.check(&TokenType::Semicolon)? {
-            self.expression()?
+        let value = if
+            !self.check(&TokenType::Semicolon)? &&
+            !self.check(&TokenType::RightBrace)? &&
+            !self.is_at_end()?
+        {
+            let value = self.expression()?;
+
+            // return a, b gives back the tuple (a, b)
+            if self.match_tokens(&[TokenType::Comma])? {
+                let mut elements = vec![value];
+
+                loop {
+                    elements.push(self.expression()?);
+
+                    if !self.match_tokens(&[TokenType::Comma])? {
+                        break;
+                    }
+                }
+
+                Expr::Tuple(TupleExpr { elements })
+            } else {
+                value
+            }
         } else {
             Expr::Literal(LiteralExpr { value: Some(Literal::Null) })
         };
 
-        self.consume(TokenType::Semicolon, "Expected ';' after return value")?;
+        self.consume_end_of_statement("Expected ';' after return value")?;
 
         Ok(Stmt::Return(ReturnStmt { keyword: keyword, value: Some(value) }))
     }
+
+    pub(super) fn yield_statement(&mut self) -> Result<Stmt, ViskumError> {
+        let keyword = self.peek_previous()?;
+        let value = if
+            !self.check(&TokenType::Semicolon)? &&
+            !self.check(&TokenType::RightBrace)? &&
+            !self.is_at_end()?
+        {
+            Some(self.expression()?)
+        } else {
+            None
+        };
+
+        self.consume_end_of_statement("Expected ';' after yielded value")?;
+
+        // The function the yield is in becomes a generator
+        self.function_yields = true;
+
+        Ok(Stmt::Yield(YieldStmt { keyword, value }))
+    }
+
+    pub(super) fn throw_statement(&mut self) -> Result<Stmt, ViskumError> {
+        let keyword = self.peek_previous()?;
+        let value = self.expression()?;
+
+        self.consume_end_of_statement("Expected ';' after thrown value")?;
+
+        Ok(Stmt::Throw(ThrowStmt { keyword, value }))
+    }
+
+    /// try { ... } catch e { ... } finally { ... }, where either catch or
+    /// finally can be left out
+    pub(super) fn try_statement(&mut self) -> Result<Stmt, ViskumError> {
+        let keyword = self.peek_previous()?;
+
+        self.consume(TokenType::LeftBrace, "Expected '{' after 'try'")?;
+        let body = self.block()?;
+
+        let (catch_name, catch_body) = if self.match_tokens(&[TokenType::Catch])? {
+            let name = self.consume_and_get(TokenType::Identifier, "Expected error name after 'catch'")?;
+            self.consume(TokenType::LeftBrace, "Expected '{' after error name")?;
+            (Some(name), Some(self.block()?))
+        } else {
+            (None, None)
+        };
+
+        let finally_body = if self.match_tokens(&[TokenType::Finally])? {
+            self.consume(TokenType::LeftBrace, "Expected '{' after 'finally'")?;
+            Some(self.block()?)
+        } else {
+            None
+        };
+
+        if catch_body.is_none() && finally_body.is_none() {
+            return Err(
+                ViskumError::new("Expected 'catch' or 'finally' after try block", keyword)
+            );
+        }
+
+        Ok(Stmt::Try(TryStmt { body, catch_name, catch_body, finally_body }))
+    }
 }
@@ -0,0 +1,104 @@
+use std::fmt;
+
+use crate::{ expr::Expr, token::{ Literal, Token } };
+
+/// A pattern in an arm of a match expression
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// Matches values equal to the literal, e.g. 1 or "a"
+    Literal(Literal),
+    /// Matches values from start up to end, e.g. 1..10 or "a"..="z"
+    Range {
+        start: Literal,
+        end: Literal,
+        inclusive: bool,
+        token: Token,
+    },
+    /// Matches a variant of an enum, e.g. Shape.Circle(r). Without
+    /// parentheses it matches the variant no matter what its fields are
+    Variant {
+        enum_expr: Expr,
+        tag: Token,
+        fields: Option<Vec<Pattern>>,
+    },
+    /// Matches anything and binds it to the name
+    Binding(Token),
+    /// Matches anything, written as _
+    Wildcard,
+    /// Matches if any of the alternatives match, e.g. "a" | "b"
+    Or(Vec<Pattern>),
+    /// Matches a tuple of the same length whose elements match, e.g. (x, 0)
+    Tuple(Vec<Pattern>),
+}
+
+impl Pattern {
+    /// The names the pattern binds when it matches. The parser rejects names
+    /// inside of a pattern with '|', so those don't bind anything
+    pub fn bindings(&self) -> Vec<&Token> {
+        match self {
+            Pattern::Binding(token) => vec![token],
+            Pattern::Variant { fields: Some(fields), .. } | Pattern::Tuple(fields) =>
+                fields
+                    .iter()
+                    .flat_map(|field| field.bindings())
+                    .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Pattern::Literal(literal) => write!(f, "{}", literal.to_repr_string()),
+            Pattern::Range { start, end, inclusive, .. } =>
+                write!(
+                    f,
+                    "{}{}{}",
+                    start.to_repr_string(),
+                    if *inclusive { "..=" } else { ".." },
+                    end.to_repr_string()
+                ),
+            Pattern::Variant { tag, fields, .. } => {
+                write!(f, ".{}", tag.lexeme)?;
+
+                if let Some(fields) = fields {
+                    let fields: Vec<String> = fields
+                        .iter()
+                        .map(|field| field.to_string())
+                        .collect();
+                    write!(f, "({})", fields.join(", "))?;
+                }
+
+                Ok(())
+            }
+            Pattern::Binding(token) => write!(f, "{}", token.lexeme),
+            Pattern::Wildcard => write!(f, "_"),
+            Pattern::Or(alternatives) => {
+                let alternatives: Vec<String> = alternatives
+                    .iter()
+                    .map(|alternative| alternative.to_string())
+                    .collect();
+                write!(f, "{}", alternatives.join(" | "))
+            }
+            Pattern::Tuple(elements) => {
+                let elements: Vec<String> = elements
+                    .iter()
+                    .map(|element| element.to_string())
+                    .collect();
+
+                if elements.len() == 1 {
+                    return write!(f, "({},)", elements[0]);
+                }
+                write!(f, "({})", elements.join(", "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expr>,
+    pub body: Expr,
+}
@@ -0,0 +1,243 @@
+use crate::{ expr::*, error_handler::ViskumError };
+
+use super::{ Resolver, ClassType, FunctionType };
+
+type Output = ();
+
+impl<'a> ExprVisitor<Output> for Resolver<'a> {
+    fn visit_binary_expr(&self, expr: &BinaryExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.left);
+        self.resolve_expr(&expr.right);
+        Ok(())
+    }
+
+    fn visit_call_expr(&self, expr: &CallExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.callee);
+        for argument in &expr.arguments {
+            self.resolve_expr(argument);
+        }
+        for argument in &expr.named {
+            self.resolve_expr(&argument.value);
+        }
+        Ok(())
+    }
+
+    fn visit_partial_expr(&self, expr: &PartialExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.callee);
+        for argument in &expr.arguments {
+            if !matches!(argument, Expr::Placeholder(_)) {
+                self.resolve_expr(argument);
+            }
+        }
+        for argument in &expr.named {
+            self.resolve_expr(&argument.value);
+        }
+        Ok(())
+    }
+
+    fn visit_placeholder_expr(&self, expr: &PlaceholderExpr) -> Result<Output, ViskumError> {
+        Err(
+            ViskumError::new(
+                "Cannot use '_' outside of the arguments of a call",
+                expr.token.clone()
+            )
+        )
+    }
+
+    fn visit_grouping_expr(&self, expr: &GroupingExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.expression);
+        Ok(())
+    }
+
+    fn visit_literal_expr(&self, _expr: &LiteralExpr) -> Result<Output, ViskumError> {
+        Ok(())
+    }
+
+    fn visit_logical_expr(&self, expr: &LogicalExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.left);
+        self.resolve_expr(&expr.right);
+        Ok(())
+    }
+
+    fn visit_prefix_expr(&self, expr: &PrefixExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.right);
+        Ok(())
+    }
+
+    fn visit_postfix_expr(&self, expr: &PostfixExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.left);
+        Ok(())
+    }
+
+    fn visit_ternary_expr(&self, expr: &TernaryExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.condition);
+        self.resolve_expr(&expr.true_expr);
+        self.resolve_expr(&expr.false_expr);
+        Ok(())
+    }
+
+    fn visit_variable_expr(&self, expr: &VariableExpr) -> Result<Output, ViskumError> {
+        if self.is_being_initialized(&expr.token) {
+            return Err(
+                ViskumError::new(
+                    format!(
+                        "Cannot read local variable '{}' in its own initializer",
+                        expr.token.lexeme
+                    ).as_str(),
+                    expr.token.clone()
+                )
+            );
+        }
+
+        self.resolve_local(&expr.token, &expr.depth);
+        Ok(())
+    }
+
+    fn visit_assign_expr(&self, expr: &AssignExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.value);
+        self.resolve_local(&expr.token, &expr.depth);
+        Ok(())
+    }
+
+    fn visit_get_expr(&self, expr: &GetExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.object);
+        Ok(())
+    }
+
+    fn visit_set_expr(&self, expr: &SetExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.value);
+        self.resolve_expr(&expr.object);
+        Ok(())
+    }
+
+    fn visit_this_expr(&self, expr: &ThisExpr) -> Result<Output, ViskumError> {
+        if self.current_class.get() == ClassType::None {
+            return Err(
+                ViskumError::new(
+                    "Cannot use 'this' outside of a class",
+                    expr.keyword.clone()
+                )
+            );
+        }
+
+        self.resolve_local(&expr.keyword, &expr.depth);
+        Ok(())
+    }
+
+    fn visit_new_expr(&self, expr: &NewExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.class);
+        for argument in &expr.arguments {
+            self.resolve_expr(argument);
+        }
+        for argument in &expr.named {
+            self.resolve_expr(&argument.value);
+        }
+        Ok(())
+    }
+
+    fn visit_list_expr(&self, expr: &ListExpr) -> Result<Output, ViskumError> {
+        for element in &expr.elements {
+            self.resolve_expr(element);
+        }
+        Ok(())
+    }
+
+    fn visit_tuple_expr(&self, expr: &TupleExpr) -> Result<Output, ViskumError> {
+        for element in &expr.elements {
+            self.resolve_expr(element);
+        }
+        Ok(())
+    }
+
+    fn visit_map_expr(&self, expr: &MapExpr) -> Result<Output, ViskumError> {
+        for (key, value) in expr.keys.iter().zip(expr.values.iter()) {
+            self.resolve_expr(key);
+            self.resolve_expr(value);
+        }
+        Ok(())
+    }
+
+    fn visit_index_expr(&self, expr: &IndexExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.object);
+        self.resolve_expr(&expr.index);
+        Ok(())
+    }
+
+    fn visit_indexset_expr(&self, expr: &IndexSetExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.value);
+        self.resolve_expr(&expr.object);
+        self.resolve_expr(&expr.index);
+        Ok(())
+    }
+
+    fn visit_lambda_expr(&self, expr: &LambdaExpr) -> Result<Output, ViskumError> {
+        self.resolve_function(&expr.declaration, FunctionType::Function);
+        Ok(())
+    }
+
+    fn visit_interpolation_expr(&self, expr: &InterpolationExpr) -> Result<Output, ViskumError> {
+        for expression in &expr.expressions {
+            self.resolve_expr(expression);
+        }
+        Ok(())
+    }
+
+    fn visit_block_expr(&self, expr: &BlockExpr) -> Result<Output, ViskumError> {
+        self.begin_scope();
+        self.resolve_statements(&expr.statements);
+        if let Some(value) = &expr.value {
+            self.resolve_expr(value);
+        }
+        self.end_scope();
+        Ok(())
+    }
+
+    fn visit_if_expr(&self, expr: &IfExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.condition);
+        self.resolve_expr(&expr.then_branch);
+        if let Some(else_branch) = &expr.else_branch {
+            self.resolve_expr(else_branch);
+        }
+        Ok(())
+    }
+
+    fn visit_loop_expr(&self, expr: &LoopExpr) -> Result<Output, ViskumError> {
+        self.resolve_loop_body(&expr.label, true, &expr.body);
+        Ok(())
+    }
+
+    fn visit_match_expr(&self, expr: &MatchExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.subject);
+
+        for arm in &expr.arms {
+            // The enums in variant patterns are evaluated outside of the arm's scope
+            self.resolve_pattern(&arm.pattern);
+
+            self.begin_scope();
+
+            for binding in arm.pattern.bindings() {
+                self.declare(binding);
+                self.define(binding);
+            }
+
+            if let Some(guard) = &arm.guard {
+                self.resolve_expr(guard);
+            }
+
+            self.resolve_expr(&arm.body);
+
+            self.end_scope();
+        }
+
+        Ok(())
+    }
+
+    fn visit_range_expr(&self, expr: &RangeExpr) -> Result<Output, ViskumError> {
+        self.resolve_expr(&expr.start);
+        self.resolve_expr(&expr.end);
+        if let Some(step) = &expr.step {
+            self.resolve_expr(step);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+use std::{ cell::Cell, collections::HashMap };
+
+use crate::{
+    stmt::{ Stmt, FunctionStmt },
+    expr::Expr,
+    token::Token,
+    error_handler::ViskumError,
+    util::report_error,
+    pattern::Pattern,
+};
+
+use super::{ Resolver, FunctionType, LoopScope };
+
+impl<'a> Resolver<'a> {
+    pub(super) fn resolve_statements(&self, statements: &[Stmt]) {
+        for statement in statements {
+            self.resolve_stmt(statement);
+        }
+    }
+
+    pub(super) fn resolve_stmt(&self, statement: &Stmt) {
+        if let Err(e) = statement.accept(self) {
+            report_error(self.error_handler, e);
+        }
+    }
+
+    pub(super) fn resolve_expr(&self, expr: &Expr) {
+        let can_yield = self.can_yield.replace(false);
+        if let Err(e) = expr.accept(self) {
+            report_error(self.error_handler, e);
+        }
+        self.can_yield.set(can_yield);
+    }
+
+    pub(super) fn begin_scope(&self) {
+        self.scopes.borrow_mut().push(HashMap::new());
+    }
+
+    pub(super) fn end_scope(&self) {
+        self.scopes.borrow_mut().pop();
+    }
+
+    pub(super) fn declare(&self, token: &Token) {
+        let mut scopes = self.scopes.borrow_mut();
+
+        if let Some(scope) = scopes.last_mut() {
+            if scope.contains_key(&token.lexeme) {
+                report_error(
+                    self.error_handler,
+                    ViskumError::new(
+                        format!(
+                            "Variable '{}' is already declared in this scope",
+                            token.lexeme
+                        ).as_str(),
+                        token.clone()
+                    )
+                );
+            }
+
+            scope.insert(token.lexeme.clone(), false);
+        }
+    }
+
+    pub(super) fn define(&self, token: &Token) {
+        if let Some(scope) = self.scopes.borrow_mut().last_mut() {
+            scope.insert(token.lexeme.clone(), true);
+        }
+    }
+
+    pub(super) fn define_name(&self, name: &str) {
+        if let Some(scope) = self.scopes.borrow_mut().last_mut() {
+            scope.insert(name.to_string(), true);
+        }
+    }
+
+    pub(super) fn is_being_initialized(&self, token: &Token) -> bool {
+        match self.scopes.borrow().last() {
+            Some(scope) => scope.get(&token.lexeme) == Some(&false),
+            None => false,
+        }
+    }
+
+    pub(super) fn resolve_local(&self, token: &Token, depth: &Cell<Option<usize>>) {
+        let scopes = self.scopes.borrow();
+
+        for (distance, scope) in scopes.iter().rev().enumerate() {
+            if scope.contains_key(&token.lexeme) {
+                depth.set(Some(distance));
+                return;
+            }
+        }
+    }
+
+    pub(super) fn resolve_pattern(&self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Variant { enum_expr, fields, .. } => {
+                self.resolve_expr(enum_expr);
+
+                for field in fields.iter().flatten() {
+                    self.resolve_pattern(field);
+                }
+            }
+            Pattern::Or(alternatives) | Pattern::Tuple(alternatives) => {
+                for alternative in alternatives {
+                    self.resolve_pattern(alternative);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    pub(super) fn resolve_function(&self, function: &FunctionStmt, function_type: FunctionType) {
+        let enclosing_function = self.current_function.replace(function_type);
+        // break and continue can't leave the function they're in
+        let enclosing_loops = self.loops.take();
+        let enclosing_can_yield = self.can_yield.replace(
+            function.generator && function_type != FunctionType::Initializer
+        );
+
+        self.begin_scope();
+        for param in &function.params {
+            // Defaults are evaluated in the function's scope, after the
+            // parameters before them are defined
+            if let Some(default) = &param.default {
+                self.resolve_expr(default);
+            }
+            self.declare(&param.token);
+            self.define(&param.token);
+        }
+        self.resolve_statements(&function.body);
+        self.end_scope();
+
+        self.current_function.set(enclosing_function);
+        self.loops.replace(enclosing_loops);
+        self.can_yield.set(enclosing_can_yield);
+    }
+
+    /// The increment of a for loop runs after the first iteration, so assigning
+    /// to an immutable loop variable in it is reported before the loop runs
+    pub(super) fn check_loop_variable_is_mutable(&self, initializer: Option<&Stmt>, increment: &Expr) {
+        let variable = match initializer {
+            Some(Stmt::Let(variable)) if !variable.mutable => variable,
+            _ => {
+                return;
+            }
+        };
+
+        if let Some(token) = assignment_to(increment, &variable.token.lexeme) {
+            report_error(
+                self.error_handler,
+                ViskumError::new(
+                    format!(
+                        "Cannot assign to immutable variable '{}' declared on line {}. Declare it with 'let mut' to make it mutable",
+                        token.lexeme,
+                        variable.token.line
+                    ).as_str(),
+                    token.clone()
+                )
+            );
+        }
+    }
+
+    pub(super) fn resolve_loop_body(&self, label: &Option<Token>, has_value: bool, body: &Stmt) {
+        self.loops.borrow_mut().push(LoopScope {
+            label: label.as_ref().map(|label| label.lexeme.clone()),
+            has_value,
+        });
+        self.resolve_stmt(body);
+        self.loops.borrow_mut().pop();
+    }
+
+    /// Finds the loop a break or continue leaves, which is the innermost one
+    /// unless it names a label
+    pub(super) fn target_loop_has_value(
+        &self,
+        keyword: &Token,
+        label: &Option<Token>
+    ) -> Result<bool, ViskumError> {
+        let loops = self.loops.borrow();
+
+        let target = match label {
+            Some(label) =>
+                loops
+                    .iter()
+                    .rev()
+                    .find(|scope| scope.label.as_ref() == Some(&label.lexeme))
+                    .ok_or_else(|| {
+                        ViskumError::new(
+                            format!("Unknown loop label {}", label.lexeme).as_str(),
+                            label.clone()
+                        )
+                    })?,
+            None =>
+                loops.last().ok_or_else(|| {
+                    ViskumError::new(
+                        format!("Cannot use '{}' outside of a loop", keyword.lexeme).as_str(),
+                        keyword.clone()
+                    )
+                })?,
+        };
+
+        Ok(target.has_value)
+    }
+}
+
+/// The variable token of an assignment to the given name in an increment like
+/// i++, i += 2 or (i++, j--)
+fn assignment_to<'e>(expr: &'e Expr, name: &str) -> Option<&'e Token> {
+    match expr {
+        Expr::Assign(assign) if assign.token.lexeme == name => Some(&assign.token),
+        Expr::Grouping(grouping) => assignment_to(&grouping.expression, name),
+        Expr::Tuple(tuple) => tuple.elements.iter().find_map(|element| assignment_to(element, name)),
+        _ => None,
+    }
+}
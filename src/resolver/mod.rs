@@ -0,0 +1,61 @@
+use std::{ cell::{ Cell, RefCell }, collections::HashMap };
+
+mod helper_methods;
+mod expr_visitor;
+mod stmt_visitor;
+
+use crate::{ error_handler::ErrorHandler, stmt::Stmt };
+
+#[derive(Clone, Copy, PartialEq)]
+enum FunctionType {
+    None,
+    Function,
+    Method,
+    Initializer,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ClassType {
+    None,
+    Class,
+}
+
+/// A loop that the break and continue statements inside of it can refer to
+struct LoopScope {
+    label: Option<String>,
+    // Only loop, and not while or for, can be broken out of with a value
+    has_value: bool,
+}
+
+/// Walks the syntax tree before it's interpreted and records, on every
+/// variable access, how many scopes out the variable was declared. Variables
+/// that aren't found in any local scope are left unresolved and treated as
+/// globals by the interpreter.
+pub struct Resolver<'a> {
+    error_handler: &'a RefCell<ErrorHandler>,
+    // The value is whether the variable has finished being initialized
+    scopes: RefCell<Vec<HashMap<String, bool>>>,
+    current_function: Cell<FunctionType>,
+    current_class: Cell<ClassType>,
+    loops: RefCell<Vec<LoopScope>>,
+    // Generators can only pause between statements, so yield isn't allowed
+    // inside of expressions or try blocks
+    can_yield: Cell<bool>,
+}
+
+impl<'a> Resolver<'a> {
+    pub fn new(error_handler: &'a RefCell<ErrorHandler>) -> Self {
+        Resolver {
+            error_handler: error_handler,
+            scopes: RefCell::new(Vec::new()),
+            current_function: Cell::new(FunctionType::None),
+            current_class: Cell::new(ClassType::None),
+            loops: RefCell::new(Vec::new()),
+            can_yield: Cell::new(false),
+        }
+    }
+
+    pub fn resolve(&self, statements: &[Stmt]) {
+        self.resolve_statements(statements);
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{ stmt::*, expr::Expr, error_handler::ViskumError, viskum_class::INITIALIZER_NAME };
+
+use super::{ Resolver, FunctionType, ClassType };
+
+type Output = ();
+
+impl<'a> StmtVisitor<Output> for Resolver<'a> {
+    fn visit_block_stmt(&self, stmt: &BlockStmt) -> Result<Output, ViskumError> {
+        self.begin_scope();
+        self.resolve_statements(&stmt.statements);
+        self.end_scope();
+        Ok(())
+    }
+
+    fn visit_expression_stmt(&self, stmt: &ExpressionStmt) -> Result<Output, ViskumError> {
+        match &stmt.expression {
+            // A loop on its own is run like a while loop, so a generator can
+            // yield inside of it
+            Expr::Loop(_) => stmt.expression.accept(self),
+            expression => {
+                self.resolve_expr(expression);
+                Ok(())
+            }
+        }
+    }
+
+    fn visit_if_stmt(&self, stmt: &IfStmt) -> Result<Output, ViskumError> {
+        self.resolve_expr(&stmt.condition);
+        self.resolve_stmt(&stmt.then_branch);
+        if let Some(else_branch) = &stmt.else_branch {
+            self.resolve_stmt(else_branch);
+        }
+        Ok(())
+    }
+
+    fn visit_print_stmt(&self, stmt: &PrintStmt) -> Result<Output, ViskumError> {
+        self.resolve_expr(&stmt.expression);
+        Ok(())
+    }
+
+    fn visit_let_stmt(&self, stmt: &LetStmt) -> Result<Output, ViskumError> {
+        self.declare(&stmt.token);
+        self.resolve_expr(&stmt.initializer);
+        self.define(&stmt.token);
+        Ok(())
+    }
+
+    fn visit_destructure_stmt(&self, stmt: &DestructureStmt) -> Result<Output, ViskumError> {
+        let names: Vec<_> = stmt.names
+            .iter()
+            .filter(|name| name.lexeme != "_")
+            .collect();
+
+        for name in &names {
+            self.declare(name);
+        }
+        self.resolve_expr(&stmt.initializer);
+        for name in &names {
+            self.define(name);
+        }
+        Ok(())
+    }
+
+    fn visit_while_stmt(&self, stmt: &WhileStmt) -> Result<Output, ViskumError> {
+        self.resolve_expr(&stmt.condition);
+        self.resolve_loop_body(&stmt.label, false, &stmt.body);
+        Ok(())
+    }
+
+    fn visit_for_stmt(&self, stmt: &ForStmt) -> Result<Output, ViskumError> {
+        self.begin_scope();
+        if let Some(initializer) = &stmt.initializer {
+            self.resolve_stmt(initializer);
+        }
+        if let Some(condition) = &stmt.condition {
+            self.resolve_expr(condition);
+        }
+        if let Some(increment) = &stmt.increment {
+            self.check_loop_variable_is_mutable(stmt.initializer.as_deref(), increment);
+            self.resolve_expr(increment);
+        }
+        self.resolve_loop_body(&stmt.label, false, &stmt.body);
+        self.end_scope();
+        Ok(())
+    }
+
+    fn visit_forin_stmt(&self, stmt: &ForInStmt) -> Result<Output, ViskumError> {
+        self.resolve_expr(&stmt.iterable);
+
+        self.begin_scope();
+        self.declare(&stmt.token);
+        self.define(&stmt.token);
+        self.resolve_loop_body(&stmt.label, false, &stmt.body);
+        self.end_scope();
+        Ok(())
+    }
+
+    fn visit_loopcontrol_stmt(&self, stmt: &LoopControlStmt) -> Result<Output, ViskumError> {
+        let has_value = self.target_loop_has_value(&stmt.keyword, &stmt.label)?;
+
+        if let Some(value) = &stmt.value {
+            if !has_value {
+                return Err(
+                    ViskumError::new(
+                        "Cannot break with a value out of a while or for loop: Only 'loop' has a value",
+                        stmt.keyword.clone()
+                    )
+                );
+            }
+            self.resolve_expr(value);
+        }
+        Ok(())
+    }
+
+    fn visit_function_stmt(&self, stmt: &FunctionStmt) -> Result<Output, ViskumError> {
+        // Defined before the body is resolved so the function can call itself
+        self.declare(&stmt.token);
+        self.define(&stmt.token);
+
+        self.resolve_function(stmt, FunctionType::Function);
+        Ok(())
+    }
+
+    fn visit_class_stmt(&self, stmt: &ClassStmt) -> Result<Output, ViskumError> {
+        let enclosing_class = self.current_class.replace(ClassType::Class);
+
+        self.declare(&stmt.token);
+        self.define(&stmt.token);
+
+        // Methods are bound to an environment containing 'this' when accessed
+        self.begin_scope();
+        self.define_name("this");
+
+        for method in &stmt.methods {
+            let function_type = if method.token.lexeme == INITIALIZER_NAME {
+                FunctionType::Initializer
+            } else {
+                FunctionType::Method
+            };
+            self.resolve_function(method, function_type);
+        }
+
+        self.end_scope();
+
+        self.current_class.set(enclosing_class);
+        Ok(())
+    }
+
+    fn visit_return_stmt(&self, stmt: &ReturnStmt) -> Result<Output, ViskumError> {
+        if self.current_function.get() == FunctionType::None {
+            return Err(
+                ViskumError::new(
+                    "Cannot return from top-level code",
+                    stmt.keyword.clone()
+                )
+            );
+        }
+
+        if let Some(value) = &stmt.value {
+            self.resolve_expr(value);
+        }
+        Ok(())
+    }
+
+    fn visit_yield_stmt(&self, stmt: &YieldStmt) -> Result<Output, ViskumError> {
+        let message = match self.current_function.get() {
+            FunctionType::None => Some("Cannot yield from top-level code"),
+            FunctionType::Initializer => Some("Cannot yield from an initializer"),
+            _ if !self.can_yield.get() =>
+                Some("Cannot yield inside of an expression or a try block"),
+            _ => None,
+        };
+
+        if let Some(message) = message {
+            return Err(ViskumError::new(message, stmt.keyword.clone()));
+        }
+
+        if let Some(value) = &stmt.value {
+            self.resolve_expr(value);
+        }
+        Ok(())
+    }
+
+    fn visit_enum_stmt(&self, stmt: &EnumStmt) -> Result<Output, ViskumError> {
+        self.declare(&stmt.token);
+        self.define(&stmt.token);
+        Ok(())
+    }
+
+    fn visit_import_stmt(&self, stmt: &ImportStmt) -> Result<Output, ViskumError> {
+        self.declare(&stmt.name);
+        self.define(&stmt.name);
+        Ok(())
+    }
+
+    fn visit_throw_stmt(&self, stmt: &ThrowStmt) -> Result<Output, ViskumError> {
+        self.resolve_expr(&stmt.value);
+        Ok(())
+    }
+
+    fn visit_try_stmt(&self, stmt: &TryStmt) -> Result<Output, ViskumError> {
+        let can_yield = self.can_yield.replace(false);
+
+        self.begin_scope();
+        self.resolve_statements(&stmt.body);
+        self.end_scope();
+
+        if let (Some(name), Some(body)) = (&stmt.catch_name, &stmt.catch_body) {
+            self.begin_scope();
+            self.declare(name);
+            self.define(name);
+            self.resolve_statements(body);
+            self.end_scope();
+        }
+
+        if let Some(body) = &stmt.finally_body {
+            self.begin_scope();
+            self.resolve_statements(body);
+            self.end_scope();
+        }
+
+        self.can_yield.set(can_yield);
+        Ok(())
+    }
+}
@@ -6,25 +6,34 @@ use crate::environment::Environment;
 use crate::error_handler::ErrorHandler;
 use crate::interpreter::Interpreter;
 use crate::parser::Parser;
+use crate::resolver::Resolver;
 use crate::print_util::print_error;
 use crate::lexer::Lexer;
+use crate::stmt::Stmt;
+use crate::viskum_module::ModuleLoader;
 
 use std::io::{ self, BufRead, Result, stdout, Write };
 use std::process;
 
+const PROMPT_FILE: &str = "prompt";
+
 pub struct Viskum {
     environment: RefCell<Rc<RefCell<Environment>>>,
+    modules: ModuleLoader,
 }
 
 impl Viskum {
     pub fn new() -> Self {
         let environment = RefCell::new(Rc::new(RefCell::new(Environment::new())));
-        Viskum { environment }
+        Viskum { environment, modules: ModuleLoader::new() }
     }
 
     pub fn run_file(&self, path: &String) -> Result<()> {
         match std::fs::read_to_string(path) {
-            Ok(str) => self.run(str.as_str()),
+            Ok(str) => {
+                self.modules.set_main_file(path);
+                self.run(str.as_str(), path)
+            }
             Err(e) => {
                 print_error(format!("There was an error while reading file: {}", e).as_str());
                 process::exit(64);
@@ -46,7 +55,7 @@ impl Viskum {
                 if line == "@" {
                     println!("{:?}", self.environment);
                 } else {
-                    self.run(line.as_str());
+                    self.run(line.as_str(), PROMPT_FILE);
                 }
             } else {
                 break;
@@ -56,33 +65,52 @@ impl Viskum {
         }
     }
 
-    pub fn run(&self, source: &str) {
-        let error_handler = RefCell::new(ErrorHandler::new());
+    pub fn run(&self, source: &str, file: &str) {
+        let error_handler = RefCell::new(ErrorHandler::new(file));
+
+        match parse_source(source, file, &error_handler) {
+            Some(stmts) => {
+                let interpreter = Interpreter::new(
+                    &error_handler,
+                    &self.environment,
+                    &self.modules
+                );
+                let _ = interpreter.interpret(stmts);
+                // AstPrinter.print(&expr);
+            }
+            None => {
+                error_handler.borrow_mut().print_errors();
+            }
+        }
+    }
+}
 
-        let mut lexer = Lexer::new(source.to_string(), &error_handler);
+/// Scans, parses and resolves the source. Returns None if any errors were
+/// reported to the error handler
+pub fn parse_source(
+    source: &str,
+    file: &str,
+    error_handler: &RefCell<ErrorHandler>
+) -> Option<Vec<Stmt>> {
+    let mut lexer = Lexer::new(source.to_string(), file, error_handler);
 
-        let tokens = lexer.scan_tokens();
+    let tokens = lexer.scan_tokens().ok()?;
 
-        if let Ok(tokens) = tokens {
-            let mut parser = Parser::new(tokens, &error_handler);
+    let mut parser = Parser::new(tokens, error_handler);
 
-            match parser.parse() {
-                Ok(stmts) => {
-                    let has_error = &error_handler.borrow().has_error();
+    match parser.parse() {
+        Ok(stmts) => {
+            Resolver::new(error_handler).resolve(&stmts);
 
-                    if !*has_error {
-                        let interpreter = Interpreter::new(&error_handler, &self.environment);
-                        let _ = interpreter.interpret(stmts);
-                        // AstPrinter.print(&expr);
-                    } else {
-                        error_handler.borrow_mut().print_errors();
-                    }
-                }
-                Err(e) => {
-                    error_handler.borrow_mut().report_error(e);
-                    error_handler.borrow_mut().print_errors();
-                }
+            if error_handler.borrow().has_error() {
+                None
+            } else {
+                Some(stmts)
             }
         }
+        Err(e) => {
+            error_handler.borrow_mut().report_error(e);
+            None
+        }
     }
 }
@@ -1,13 +1,35 @@
-use std::fmt;
+use std::{ fmt, rc::Rc, cell::RefCell };
 
-use crate::viskum_callable::Callable;
+use crate::{
+    viskum_callable::Callable,
+    viskum_class::ViskumClass,
+    viskum_instance::ViskumInstance,
+    viskum_map::ViskumMap,
+    viskum_enum::{ ViskumEnum, ViskumVariant },
+    viskum_module::ViskumModule,
+    viskum_exception::ViskumException,
+    viskum_range::ViskumRange,
+    viskum_iterator::ViskumIterator,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Literal {
+    Int(i64),
     Num(f64),
     Str(String),
     Bool(bool),
     Func(Callable),
+    Class(ViskumClass),
+    Instance(Rc<RefCell<ViskumInstance>>),
+    List(Rc<RefCell<Vec<Literal>>>),
+    Tuple(Rc<Vec<Literal>>),
+    Map(Rc<RefCell<ViskumMap>>),
+    Range(ViskumRange),
+    Iterator(Rc<ViskumIterator>),
+    Enum(ViskumEnum),
+    Variant(Rc<ViskumVariant>),
+    Module(Rc<ViskumModule>),
+    Exception(Rc<ViskumException>),
     Null,
 }
 
@@ -16,28 +38,134 @@ impl Literal {
         match self {
             Literal::Bool(_) => "bool".to_string(),
             Literal::Null => "null".to_string(),
-            Literal::Num(_) => "number".to_string(),
+            Literal::Int(_) => "int".to_string(),
+            Literal::Num(_) => "float".to_string(),
             Literal::Str(_) => "string".to_string(),
             Literal::Func(_) => "function".to_string(),
+            Literal::Class(_) => "class".to_string(),
+            Literal::Instance(_) => "instance".to_string(),
+            Literal::List(_) => "list".to_string(),
+            Literal::Tuple(_) => "tuple".to_string(),
+            Literal::Map(_) => "map".to_string(),
+            Literal::Range(_) => "range".to_string(),
+            Literal::Iterator(_) => "iterator".to_string(),
+            Literal::Enum(_) => "enum".to_string(),
+            Literal::Variant(_) => "enum variant".to_string(),
+            Literal::Module(_) => "module".to_string(),
+            Literal::Exception(_) => "error".to_string(),
         }
     }
 
-    pub fn to_num(&self) -> Result<f64, ()> {
+    pub fn new_list(elements: Vec<Literal>) -> Literal {
+        Literal::List(Rc::new(RefCell::new(elements)))
+    }
+
+    pub fn new_tuple(elements: Vec<Literal>) -> Literal {
+        Literal::Tuple(Rc::new(elements))
+    }
+
+    /// Formats the value the way it's shown inside of a collection, where
+    /// strings are quoted
+    pub fn to_repr_string(&self) -> String {
         match self {
-            Literal::Num(x) => Ok(*x),
-            _ => Err(()),
+            Literal::Str(str) => format!("{:?}", str),
+            _ => self.to_string(),
         }
     }
 }
 
+thread_local! {
+    static FORMATTING: RefCell<Vec<*const ()>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Marks a list or map as being formatted for as long as it's alive, so one
+/// that contains itself is shown as [...] instead of being formatted forever
+struct Formatting(*const ());
+
+impl Formatting {
+    fn enter(collection: *const ()) -> Option<Formatting> {
+        FORMATTING.with(|formatting| {
+            let mut formatting = formatting.borrow_mut();
+            if formatting.contains(&collection) {
+                return None;
+            }
+            formatting.push(collection);
+            Some(Formatting(collection))
+        })
+    }
+}
+
+impl Drop for Formatting {
+    fn drop(&mut self) {
+        FORMATTING.with(|formatting| formatting.borrow_mut().retain(|collection| *collection != self.0));
+    }
+}
+
 impl fmt::Display for Literal {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Literal::Num(x) => write!(f, "{x}"),
+            Literal::Int(x) => write!(f, "{x}"),
+            // Debug formatting keeps the decimal point, so 3.0 isn't shown like the int 3
+            Literal::Num(x) => write!(f, "{x:?}"),
             Literal::Str(str) => write!(f, "{str}"),
             Literal::Null => write!(f, "null"),
             Literal::Bool(b) => if *b { write!(f, "true") } else { write!(f, "false") }
             Literal::Func(func) => write!(f, "{:?}", func),
+            Literal::Class(class) => write!(f, "{:?}", class),
+            Literal::Instance(instance) => write!(f, "{:?}", instance.borrow()),
+            Literal::Enum(enum_type) => write!(f, "{:?}", enum_type),
+            Literal::Variant(variant) => write!(f, "{:?}", variant),
+            Literal::Module(module) => write!(f, "{:?}", module),
+            Literal::Exception(exception) => write!(f, "{:?}", exception),
+            Literal::Range(range) => write!(f, "{}", range),
+            Literal::Iterator(iterator) => write!(f, "{:?}", iterator),
+            Literal::List(list) => {
+                let _formatting = match Formatting::enter(Rc::as_ptr(list).cast()) {
+                    Some(formatting) => formatting,
+                    None => {
+                        return write!(f, "[...]");
+                    }
+                };
+
+                let elements: Vec<String> = list
+                    .borrow()
+                    .iter()
+                    .map(|element| element.to_repr_string())
+                    .collect();
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Literal::Tuple(tuple) => {
+                let elements: Vec<String> = tuple
+                    .iter()
+                    .map(|element| element.to_repr_string())
+                    .collect();
+
+                // (1,) so a tuple of one isn't shown like a grouped value
+                if elements.len() == 1 {
+                    return write!(f, "({},)", elements[0]);
+                }
+                write!(f, "({})", elements.join(", "))
+            }
+            Literal::Map(map) => {
+                let _formatting = match Formatting::enter(Rc::as_ptr(map).cast()) {
+                    Some(formatting) => formatting,
+                    None => {
+                        return write!(f, "[...]");
+                    }
+                };
+                let map = map.borrow();
+
+                if map.len() == 0 {
+                    return write!(f, "[:]");
+                }
+
+                let entries: Vec<String> = map
+                    .entries()
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key.to_repr_string(), value.to_repr_string()))
+                    .collect();
+                write!(f, "[{}]", entries.join(", "))
+            }
         }
     }
 }
@@ -1,7 +1,7 @@
 mod token_type;
 mod literal;
 
-use std::fmt;
+use std::{ fmt, rc::Rc };
 
 pub use self::token_type::TokenType;
 pub use self::literal::Literal;
@@ -12,23 +12,47 @@ pub struct Token {
     pub lexeme: String,
     pub literal: Option<Literal>,
     pub line: usize,
+    /// The file the token was scanned from. Empty for tokens made by the interpreter
+    pub file: Rc<str>,
+    /// Whether the lexer inferred the token from a line break instead of
+    /// scanning it, like the ';' ending a statement at the end of its line
+    pub inferred: bool,
 }
 
 impl Token {
-    pub fn new(ttype: TokenType, lexeme: String, literal: Option<Literal>, line: usize) -> Self {
-        Token { ttype, lexeme, literal, line }
+    pub fn new(
+        ttype: TokenType,
+        lexeme: String,
+        literal: Option<Literal>,
+        line: usize,
+        file: Rc<str>
+    ) -> Self {
+        Token { ttype, lexeme, literal, line, file, inferred: false }
     }
 
     pub fn invalid(line: Option<usize>) -> Token {
-        Token::new(TokenType::Invalid, "".to_string(), None, if let Some(line) = line {
-            line
-        } else {
-            0
-        })
+        Token::new(
+            TokenType::Invalid,
+            "".to_string(),
+            None,
+            if let Some(line) = line {
+                line
+            } else {
+                0
+            },
+            Rc::from("")
+        )
+    }
+
+    pub fn eof(line: usize, file: Rc<str>) -> Token {
+        Token::new(TokenType::Eof, "".to_string(), None, line, file)
     }
 
-    pub fn eof(line: usize) -> Token {
-        Token::new(TokenType::Eof, "".to_string(), None, line)
+    pub fn newline(line: usize, file: Rc<str>) -> Token {
+        Token {
+            inferred: true,
+            ..Token::new(TokenType::Semicolon, "newline".to_string(), None, line, file)
+        }
     }
 
     pub fn is(&self, ttype: TokenType) -> bool {
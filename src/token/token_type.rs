@@ -4,8 +4,13 @@ pub enum TokenType {
     RightParen,
     LeftBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
     Comma,
     Dot,
+    DotDot,
+    DotDotEqual,
+    Ellipsis,
     Minus,
     Plus,
     Semicolon,
@@ -14,8 +19,16 @@ pub enum TokenType {
     Slash,
     Star,
     Power,
+    Percent,
+    TildeSlash,
     Factorial,
 
+    Ampersand,
+    Pipe,
+    Tilde,
+    ShiftLeft,
+    ShiftRight,
+
     Increment,
     Decrement,
     PlusEqual,
@@ -23,18 +36,28 @@ pub enum TokenType {
     StarEqual,
     SlashEqual,
     PowerEqual,
+    PercentEqual,
+    TildeSlashEqual,
+    AmpersandEqual,
+    PipeEqual,
+    ShiftLeftEqual,
+    ShiftRightEqual,
 
     Bang,
     BangEqual,
     Equal,
     EqualEqual,
+    FatArrow,
+    PipeGreater,
     Greater,
     GreaterEqual,
     Less,
     LessEqual,
 
     Identifier,
+    Label,
     String,
+    Interpolation,
     Number,
 
     And,
@@ -42,6 +65,7 @@ pub enum TokenType {
     Else,
     False,
     For,
+    In,
     If,
     Null,
     Or,
@@ -57,6 +81,15 @@ pub enum TokenType {
     Continue,
     Loop,
     Fn,
+    Match,
+    Enum,
+    Import,
+    As,
+    Throw,
+    Try,
+    Catch,
+    Finally,
+    Yield,
 
     Eof,
 
@@ -82,8 +115,13 @@ impl TokenType {
             Self::RightParen => ")",
             Self::LeftBrace => "{",
             Self::RightBrace => "}",
+            Self::LeftBracket => "[",
+            Self::RightBracket => "]",
             Self::Comma => ",",
             Self::Dot => ".",
+            Self::DotDot => "..",
+            Self::DotDotEqual => "..=",
+            Self::Ellipsis => "...",
             Self::Minus => "-",
             Self::Plus => "+",
             Self::Semicolon => ";",
@@ -92,8 +130,16 @@ impl TokenType {
             Self::Slash => "/",
             Self::Star => "*",
             Self::Power => "^",
+            Self::Percent => "%",
+            Self::TildeSlash => "~/",
             Self::Factorial => "!",
 
+            Self::Ampersand => "&",
+            Self::Pipe => "|",
+            Self::Tilde => "~",
+            Self::ShiftLeft => "<<",
+            Self::ShiftRight => ">>",
+
             Self::Increment => "++",
             Self::Decrement => "--",
             Self::PlusEqual => "+=",
@@ -101,18 +147,28 @@ impl TokenType {
             Self::StarEqual => "*=",
             Self::SlashEqual => "/=",
             Self::PowerEqual => "^=",
+            Self::PercentEqual => "%=",
+            Self::TildeSlashEqual => "~/=",
+            Self::AmpersandEqual => "&=",
+            Self::PipeEqual => "|=",
+            Self::ShiftLeftEqual => "<<=",
+            Self::ShiftRightEqual => ">>=",
 
             Self::Bang => "!",
             Self::BangEqual => "!=",
             Self::Equal => "=",
             Self::EqualEqual => "==",
+            Self::FatArrow => "=>",
+            Self::PipeGreater => "|>",
             Self::Greater => ">",
             Self::GreaterEqual => ">=",
             Self::Less => "<",
             Self::LessEqual => "<=",
 
             Self::Identifier => "identifier",
+            Self::Label => "label",
             Self::String => "string",
+            Self::Interpolation => "interpolated string",
             Self::Number => "number",
 
             Self::And => "and",
@@ -120,6 +176,7 @@ impl TokenType {
             Self::Else => "else",
             Self::False => "false",
             Self::For => "for",
+            Self::In => "in",
             Self::If => "if",
             Self::Null => "null",
             Self::Or => "or",
@@ -135,6 +192,15 @@ impl TokenType {
             Self::Continue => "continue",
             Self::Loop => "loop",
             Self::Fn => "fn",
+            Self::Match => "match",
+            Self::Enum => "enum",
+            Self::Import => "import",
+            Self::As => "as",
+            Self::Throw => "throw",
+            Self::Try => "try",
+            Self::Catch => "catch",
+            Self::Finally => "finally",
+            Self::Yield => "yield",
 
             Self::Eof => "end of file",
 
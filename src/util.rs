@@ -10,20 +10,28 @@ pub fn is_alphabetic(ch: Option<char>) -> bool {
     if let Some(ch) = ch { ch.is_ascii_alphabetic() || ch == '_' } else { false }
 }
 
+pub fn is_identifier(str: &str) -> bool {
+    let mut chars = str.chars();
+
+    is_alphabetic(chars.next()) && chars.all(|ch| is_alphabetic(Some(ch)) || is_digit(Some(ch)))
+}
+
 pub fn report_error(error_handler: &RefCell<ErrorHandler>, viskum_error: ViskumError) {
     error_handler.borrow_mut().report_error(viskum_error)
 }
 
 pub fn factorial(n: f64) -> f64 {
-    if n < 0.0 {
+    if n < 0.0 || n > 170.0 {
+        // 171! is too large for a float
         std::f64::INFINITY
     } else if n.fract() != 0.0 {
         gamma(n + 1.0)
-    } else if n == 0.0 {
-        1.0
     } else {
-        let num = n.round() as u128;
-        let factorial: u128 = (1..=num).product();
-        factorial as f64
+        (1..=n as u64).map(|x| x as f64).product()
     }
 }
+
+/// Returns None if the result is too large for an int
+pub fn int_factorial(n: i64) -> Option<i64> {
+    (1..=n).try_fold(1i64, |product, x| product.checked_mul(x))
+}
@@ -1,11 +1,43 @@
-use std::rc::Rc;
+use std::{ fmt, rc::Rc };
 
-use crate::{ interpreter::Interpreter, token::Literal, error_handler::ViskumError };
+use crate::{
+    interpreter::Interpreter,
+    token::{ Literal, Token },
+    error_handler::ViskumError,
+};
 
 #[derive(Clone)]
 pub struct Callable {
     pub func: Rc<dyn ViskumCallable>,
-    pub arity: usize,
+    pub arity: Arity,
+}
+
+/// How many arguments a callable accepts. There's no maximum for functions
+/// with a rest parameter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Arity {
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+impl Arity {
+    pub fn exactly(count: usize) -> Self {
+        Arity { min: count, max: Some(count) }
+    }
+
+    pub fn accepts(&self, count: usize) -> bool {
+        count >= self.min && self.max.is_none_or(|max| count <= max)
+    }
+}
+
+impl fmt::Display for Arity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.max {
+            Some(max) if max == self.min => write!(f, "{}", max),
+            Some(max) => write!(f, "{} to {}", self.min, max),
+            None => write!(f, "at least {}", self.min),
+        }
+    }
 }
 
 pub trait ViskumCallable {
@@ -14,7 +46,38 @@ pub trait ViskumCallable {
         interpreter: &Interpreter,
         arguments: &Vec<Literal>
     ) -> Result<Literal, ViskumError>;
-    fn arity(&self) -> usize;
+    fn arity(&self) -> Arity;
+
+    /// Like `call`, but a call in tail position may be handed back as an
+    /// `AbortReason::TailCall` error instead of being performed. Only the
+    /// trampoline in `ViskumFunction::call` should use this.
+    fn call_step(
+        &self,
+        interpreter: &Interpreter,
+        arguments: &Vec<Literal>
+    ) -> Result<Literal, ViskumError> {
+        self.call(interpreter, arguments)
+    }
+
+    /// Like `call`, with arguments given by the name of their parameter after
+    /// the positional ones. Native functions don't have named parameters
+    fn call_named(
+        &self,
+        interpreter: &Interpreter,
+        arguments: &Vec<Literal>,
+        named: &[(Token, Literal)]
+    ) -> Result<Literal, ViskumError> {
+        match named.first() {
+            None => self.call(interpreter, arguments),
+            Some((name, _)) =>
+                Err(
+                    ViskumError::new(
+                        format!("{} doesn't take named arguments", self.to_string()).as_str(),
+                        name.clone()
+                    )
+                ),
+        }
+    }
 
     fn to_string(&self) -> String {
         "<native fn>".to_string()
@@ -30,10 +93,19 @@ impl ViskumCallable for Callable {
         self.func.call(interpreter, arguments)
     }
 
-    fn arity(&self) -> usize {
+    fn arity(&self) -> Arity {
         self.arity
     }
 
+    fn call_named(
+        &self,
+        interpreter: &Interpreter,
+        arguments: &Vec<Literal>,
+        named: &[(Token, Literal)]
+    ) -> Result<Literal, ViskumError> {
+        self.func.call_named(interpreter, arguments, named)
+    }
+
     fn to_string(&self) -> String {
         self.func.to_string()
     }
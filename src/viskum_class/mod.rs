@@ -0,0 +1,88 @@
+use std::{ rc::Rc, cell::RefCell, collections::HashMap };
+
+use crate::{
+    viskum_callable::{ ViskumCallable, Arity },
+    viskum_function::ViskumFunction,
+    viskum_instance::ViskumInstance,
+    interpreter::Interpreter,
+    token::{ Literal, Token },
+    error_handler::ViskumError,
+};
+
+pub const INITIALIZER_NAME: &str = "init";
+
+#[derive(Clone)]
+pub struct ViskumClass {
+    pub name: String,
+    methods: Rc<HashMap<String, ViskumFunction>>,
+}
+
+impl ViskumClass {
+    pub fn new(name: String, methods: HashMap<String, ViskumFunction>) -> Self {
+        ViskumClass { name, methods: Rc::new(methods) }
+    }
+
+    pub fn find_method(&self, name: &str) -> Option<&ViskumFunction> {
+        self.methods.get(name)
+    }
+}
+
+impl ViskumCallable for ViskumClass {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        arguments: &Vec<Literal>
+    ) -> Result<Literal, ViskumError> {
+        self.call_named(interpreter, arguments, &[])
+    }
+
+    fn call_named(
+        &self,
+        interpreter: &Interpreter,
+        arguments: &Vec<Literal>,
+        named: &[(Token, Literal)]
+    ) -> Result<Literal, ViskumError> {
+        let instance = Literal::Instance(Rc::new(RefCell::new(ViskumInstance::new(self.clone()))));
+
+        match self.find_method(INITIALIZER_NAME) {
+            Some(initializer) => {
+                initializer.bind(instance.clone()).call_named(interpreter, arguments, named)?;
+            }
+            None => {
+                if let Some((name, _)) = named.first() {
+                    return Err(
+                        ViskumError::new(
+                            format!("{} has no parameter named '{}'", self.to_string(), name.lexeme).as_str(),
+                            name.clone()
+                        )
+                    );
+                }
+            }
+        }
+
+        Ok(instance)
+    }
+
+    fn arity(&self) -> Arity {
+        match self.find_method(INITIALIZER_NAME) {
+            Some(initializer) => initializer.arity(),
+            None => Arity::exactly(0),
+        }
+    }
+
+    fn to_string(&self) -> String {
+        format!("<class {}>", self.name)
+    }
+}
+
+impl PartialEq for ViskumClass {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.methods, &other.methods)
+    }
+}
+
+impl std::fmt::Debug for ViskumClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<class {}>", self.name)
+    }
+}
@@ -0,0 +1,163 @@
+use std::rc::Rc;
+
+use crate::{
+    viskum_callable::{ Callable, ViskumCallable, Arity },
+    interpreter::Interpreter,
+    token::{ Literal, Token },
+    error_handler::ViskumError,
+};
+
+/// A variant in an enum declaration, e.g. Rect(w, h)
+#[derive(Debug, Clone)]
+pub struct VariantDeclaration {
+    pub token: Token,
+    pub fields: Vec<Token>,
+}
+
+#[derive(Clone)]
+pub struct ViskumEnum {
+    pub name: String,
+    variants: Rc<Vec<VariantDeclaration>>,
+}
+
+impl ViskumEnum {
+    pub fn new(name: String, variants: Vec<VariantDeclaration>) -> Self {
+        ViskumEnum { name, variants: Rc::new(variants) }
+    }
+
+    pub fn find_variant(&self, tag: &str) -> Option<usize> {
+        self.variants.iter().position(|variant| variant.token.lexeme == tag)
+    }
+
+    /// A variant with fields gives its constructor, and a variant without any
+    /// gives the variant itself
+    pub fn get(&self, token: &Token) -> Result<Literal, ViskumError> {
+        let index = match self.find_variant(&token.lexeme) {
+            Some(index) => index,
+            None => {
+                return Err(self.undefined_variant_error(token));
+            }
+        };
+
+        let arity = self.variants[index].fields.len();
+
+        if arity == 0 {
+            return Ok(ViskumVariant::new_literal(self.clone(), index, Vec::new()));
+        }
+
+        Ok(
+            Literal::Func(Callable {
+                func: Rc::new(VariantConstructor { enum_type: self.clone(), index }),
+                arity: Arity::exactly(arity),
+            })
+        )
+    }
+
+    pub fn undefined_variant_error(&self, token: &Token) -> ViskumError {
+        ViskumError::new(
+            format!("Enum {} has no variant '{}'", self.name, token.lexeme).as_str(),
+            token.clone()
+        )
+    }
+}
+
+impl PartialEq for ViskumEnum {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.variants, &other.variants)
+    }
+}
+
+impl std::fmt::Debug for ViskumEnum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<enum {}>", self.name)
+    }
+}
+
+/// A value of an enum, e.g. Shape.Circle(2)
+#[derive(PartialEq)]
+pub struct ViskumVariant {
+    pub enum_type: ViskumEnum,
+    index: usize,
+    pub values: Vec<Literal>,
+}
+
+impl ViskumVariant {
+    pub fn new_literal(enum_type: ViskumEnum, index: usize, values: Vec<Literal>) -> Literal {
+        Literal::Variant(Rc::new(ViskumVariant { enum_type, index, values }))
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.declaration().token.lexeme
+    }
+
+    pub fn is_variant(&self, enum_type: &ViskumEnum, index: usize) -> bool {
+        &self.enum_type == enum_type && self.index == index
+    }
+
+    fn declaration(&self) -> &VariantDeclaration {
+        &self.enum_type.variants[self.index]
+    }
+
+    /// Reads a field of the payload by the name it was declared with
+    pub fn get(&self, token: &Token) -> Result<Literal, ViskumError> {
+        let position = self
+            .declaration()
+            .fields.iter()
+            .position(|field| field.lexeme == token.lexeme);
+
+        match position {
+            Some(position) => Ok(self.values[position].clone()),
+            None =>
+                Err(
+                    ViskumError::new(
+                        format!(
+                            "{}.{} has no field '{}'",
+                            self.enum_type.name,
+                            self.tag(),
+                            token.lexeme
+                        ).as_str(),
+                        token.clone()
+                    )
+                ),
+        }
+    }
+}
+
+impl std::fmt::Debug for ViskumVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.enum_type.name, self.tag())?;
+
+        if !self.values.is_empty() {
+            let values: Vec<String> = self.values
+                .iter()
+                .map(|value| value.to_repr_string())
+                .collect();
+            write!(f, "({})", values.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+struct VariantConstructor {
+    enum_type: ViskumEnum,
+    index: usize,
+}
+
+impl ViskumCallable for VariantConstructor {
+    fn call(
+        &self,
+        _interpreter: &Interpreter,
+        arguments: &Vec<Literal>
+    ) -> Result<Literal, ViskumError> {
+        Ok(ViskumVariant::new_literal(self.enum_type.clone(), self.index, arguments.clone()))
+    }
+
+    fn arity(&self) -> Arity {
+        Arity::exactly(self.enum_type.variants[self.index].fields.len())
+    }
+
+    fn to_string(&self) -> String {
+        format!("<variant {}.{}>", self.enum_type.name, self.enum_type.variants[self.index].token.lexeme)
+    }
+}
@@ -0,0 +1,39 @@
+use std::rc::Rc;
+
+use crate::{ token::{ Literal, Token }, error_handler::ViskumError };
+
+/// The value a catch clause gets. It's made from either a thrown value or an
+/// error from the interpreter, in which case its value is null
+#[derive(PartialEq)]
+pub struct ViskumException {
+    pub message: String,
+    pub line: usize,
+    pub value: Literal,
+}
+
+impl ViskumException {
+    pub fn new_literal(message: String, line: usize, value: Literal) -> Literal {
+        Literal::Exception(Rc::new(ViskumException { message, line, value }))
+    }
+
+    pub fn get(&self, token: &Token) -> Result<Literal, ViskumError> {
+        match token.lexeme.as_str() {
+            "message" => Ok(Literal::Str(self.message.clone())),
+            "line" => Ok(Literal::Int(self.line as i64)),
+            "value" => Ok(self.value.clone()),
+            _ =>
+                Err(
+                    ViskumError::new(
+                        format!("Error has no field '{}'", token.lexeme).as_str(),
+                        token.clone()
+                    )
+                ),
+        }
+    }
+}
+
+impl std::fmt::Debug for ViskumException {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<error: {}>", self.message)
+    }
+}
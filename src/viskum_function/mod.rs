@@ -1,55 +1,304 @@
 use std::{ rc::Rc, cell::RefCell };
 
 use crate::{
-    viskum_callable::ViskumCallable,
+    viskum_callable::{ ViskumCallable, Arity },
     stmt::FunctionStmt,
+    expr::Expr,
     interpreter::Interpreter,
-    token::Literal,
-    error_handler::{ ViskumError, AbortReason },
+    token::{ Literal, Token, TokenType },
+    error_handler::ViskumError,
     environment::{ Environment, environment_value::EnvironmentValue },
+    viskum_generator::ViskumGenerator,
+    viskum_iterator::ViskumIterator,
 };
 
+/// A parameter in a function declaration, e.g. x, greeting = "hi" or ...rest
+#[derive(Debug, Clone)]
+pub struct Parameter {
+    pub token: Token,
+    pub default: Option<Expr>,
+    pub rest: bool,
+}
+
+/// An argument given by the name of its parameter, e.g. greeting: "hey"
+#[derive(Debug, Clone)]
+pub struct NamedArgument {
+    pub token: Token,
+    pub value: Expr,
+}
+
 #[derive(Clone)]
 pub struct ViskumFunction {
-    declaration: FunctionStmt,
+    declaration: Rc<FunctionStmt>,
     closure: Rc<RefCell<Environment>>,
+    is_initializer: bool,
 }
 
 impl ViskumFunction {
     pub fn new(declaration: FunctionStmt, environment: Rc<RefCell<Environment>>) -> Self {
-        ViskumFunction { declaration: declaration, closure: environment }
+        ViskumFunction {
+            declaration: Rc::new(declaration),
+            closure: environment,
+            is_initializer: false,
+        }
     }
-}
 
-impl ViskumCallable for ViskumFunction {
-    fn call(&self, interpreter: &Interpreter, args: &Vec<Literal>) -> Result<Literal, ViskumError> {
+    pub fn new_method(
+        declaration: FunctionStmt,
+        environment: Rc<RefCell<Environment>>,
+        is_initializer: bool
+    ) -> Self {
+        ViskumFunction {
+            declaration: Rc::new(declaration),
+            closure: environment,
+            is_initializer,
+        }
+    }
+
+    pub fn bind(&self, instance: Literal) -> ViskumFunction {
+        let mut environment = Environment::new_with_enclosing(self.closure.clone());
+        environment.define_function("this", instance);
+
+        ViskumFunction {
+            declaration: self.declaration.clone(),
+            closure: Rc::new(RefCell::new(environment)),
+            is_initializer: self.is_initializer,
+        }
+    }
+
+    fn this(&self) -> Result<Literal, ViskumError> {
+        let token = Token::new(
+            TokenType::This,
+            "this".to_string(),
+            None,
+            self.declaration.token.line,
+            self.declaration.token.file.clone()
+        );
+        self.closure.borrow().get(&token)
+    }
+
+    /// Defines the parameters in a new environment. Arguments are matched
+    /// by position first and then by name, any that are left over go into the
+    /// rest parameter, and parameters without an argument get their default
+    /// value. Defaults are evaluated in the new environment, so they can use
+    /// the parameters before them
+    fn bind_arguments(
+        &self,
+        interpreter: &Interpreter,
+        arguments: &[Literal],
+        named: &[(Token, Literal)]
+    ) -> Result<Rc<RefCell<Environment>>, ViskumError> {
+        let params = &self.declaration.params;
+        let positional_count = params
+            .iter()
+            .filter(|param| !param.rest)
+            .count();
+
+        let mut values: Vec<Option<Literal>> = vec![None; positional_count];
+        let mut rest = Vec::new();
+
+        for (i, argument) in arguments.iter().enumerate() {
+            match values.get_mut(i) {
+                Some(value) => {
+                    *value = Some(argument.clone());
+                }
+                None => rest.push(argument.clone()),
+            }
+        }
+
+        for (name, argument) in named {
+            let position = params
+                .iter()
+                .position(|param| !param.rest && param.token.lexeme == name.lexeme);
+
+            match position {
+                Some(i) if values[i].is_some() => {
+                    return Err(
+                        ViskumError::new(
+                            format!("Parameter '{}' was given more than one argument", name.lexeme).as_str(),
+                            name.clone()
+                        )
+                    );
+                }
+                Some(i) => {
+                    values[i] = Some(argument.clone());
+                }
+                None => {
+                    return Err(
+                        ViskumError::new(
+                            format!(
+                                "{} has no parameter named '{}'",
+                                self.to_string(),
+                                name.lexeme
+                            ).as_str(),
+                            name.clone()
+                        )
+                    );
+                }
+            }
+        }
+
         let environment = Rc::new(
             RefCell::new(Environment::new_with_enclosing(self.closure.clone()))
         );
+        let mut values = values.into_iter();
 
-        for (i, param) in self.declaration.params.iter().enumerate() {
-            environment.borrow_mut().define(param, EnvironmentValue::new(args[i].clone(), false))?;
+        for param in params {
+            let value = if param.rest {
+                Literal::new_list(std::mem::take(&mut rest))
+            } else {
+                match (values.next().flatten(), &param.default) {
+                    (Some(value), _) => value,
+                    (None, Some(default)) =>
+                        interpreter.with_environment(environment.clone(), || {
+                            interpreter.evaluate(default)
+                        })?,
+                    (None, None) => {
+                        return Err(
+                            ViskumError::new(
+                                format!(
+                                    "Missing argument for parameter '{}' of {}",
+                                    param.token.lexeme,
+                                    self.to_string()
+                                ).as_str(),
+                                Token::invalid(None)
+                            )
+                        );
+                    }
+                }
+            };
+
+            environment.borrow_mut().define(&param.token, EnvironmentValue::new(value, false))?;
         }
 
-        match interpreter.execute_block(&self.declaration.body, environment) {
-            Ok(_) => (),
-            Err(e) => {
-                if let Some(abort_value) = e.get_abort_value() {
-                    if e.is_abort_error(AbortReason::Return(abort_value.clone())) {
-                        return Ok(abort_value);
-                    }
+        Ok(environment)
+    }
+
+    /// Runs the body in the environment with the parameters. A generator's
+    /// body instead runs a piece at a time as its values are asked for
+    fn run(
+        &self,
+        interpreter: &Interpreter,
+        environment: Rc<RefCell<Environment>>
+    ) -> Result<Literal, ViskumError> {
+        if self.declaration.generator {
+            return Ok(self.generator(environment));
+        }
+
+        let result = match interpreter.execute_block(&self.declaration.body, environment) {
+            Ok(_) => Ok(Literal::Null),
+            Err(e) =>
+                match e.get_abort_value() {
+                    Some(abort_value) => Ok(abort_value),
+                    None => Err(e),
                 }
-            }
+        };
+
+        if self.is_initializer {
+            return self.finish_initializer(interpreter, result);
         }
 
-        Ok(Literal::Null)
+        result
+    }
+
+    /// Out of line like `finish_initializer`, so that making a generator doesn't
+    /// take up room in the frame of every call
+    #[inline(never)]
+    fn generator(&self, environment: Rc<RefCell<Environment>>) -> Literal {
+        let generator = ViskumGenerator::new(self.declaration.clone(), environment);
+        ViskumIterator::new_literal(generator)
+    }
+
+    /// An initializer always evaluates to the instance, so a tail call inside
+    /// of it has to be performed before returning. Out of line, since every
+    /// call runs through the frame of `run`
+    #[inline(never)]
+    fn finish_initializer(
+        &self,
+        interpreter: &Interpreter,
+        result: Result<Literal, ViskumError>
+    ) -> Result<Literal, ViskumError> {
+        if let Err(e) = result {
+            let (callable, arguments, call_site) = e.into_tail_call()?;
+            callable.call(interpreter, &arguments).map_err(|e| e.with_call_site(&call_site))?;
+        }
+
+        self.this()
+    }
+}
+
+impl ViskumCallable for ViskumFunction {
+    fn call(&self, interpreter: &Interpreter, args: &Vec<Literal>) -> Result<Literal, ViskumError> {
+        self.call_named(interpreter, args, &[])
+    }
+
+    fn call_named(
+        &self,
+        interpreter: &Interpreter,
+        args: &Vec<Literal>,
+        named: &[(Token, Literal)]
+    ) -> Result<Literal, ViskumError> {
+        let environment = self.bind_arguments(interpreter, args, named)?;
+
+        perform_tail_calls(interpreter, self.run(interpreter, environment))
     }
 
-    fn arity(&self) -> usize {
-        self.declaration.params.len()
+    fn call_step(
+        &self,
+        interpreter: &Interpreter,
+        args: &Vec<Literal>
+    ) -> Result<Literal, ViskumError> {
+        let environment = self.bind_arguments(interpreter, args, &[])?;
+
+        self.run(interpreter, environment)
+    }
+
+    fn arity(&self) -> Arity {
+        let params = &self.declaration.params;
+
+        Arity {
+            min: params
+                .iter()
+                .filter(|param| !param.rest && param.default.is_none())
+                .count(),
+            max: if params.iter().any(|param| param.rest) { None } else { Some(params.len()) },
+        }
     }
 
     fn to_string(&self) -> String {
-        format!("<fn {}>", self.declaration.token.lexeme)
+        if self.declaration.token.is(TokenType::Fn) {
+            "<anonymous fn>".to_string()
+        } else {
+            format!("<fn {}>", self.declaration.token.lexeme)
+        }
+    }
+}
+
+/// Calls in tail position come back as errors instead of being performed by
+/// the callee, so that deep tail recursion runs in constant stack space. Each
+/// step's errors belong to the call that made it, not the outer one. Being a
+/// function of its own, its locals are off the stack while a body runs
+#[inline(never)]
+fn perform_tail_calls(
+    interpreter: &Interpreter,
+    mut result: Result<Literal, ViskumError>
+) -> Result<Literal, ViskumError> {
+    loop {
+        match result {
+            Err(e) =>
+                match e.into_tail_call() {
+                    Ok((callable, arguments, call_site)) => {
+                        result = callable.func
+                            .call_step(interpreter, &arguments)
+                            .map_err(|e| e.with_call_site(&call_site));
+                    }
+                    Err(e) => {
+                        return Err(e);
+                    }
+                }
+            Ok(value) => {
+                return Ok(value);
+            }
+        }
     }
 }
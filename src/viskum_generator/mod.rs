@@ -0,0 +1,355 @@
+use std::{ rc::Rc, cell::{ Cell, RefCell } };
+
+use crate::{
+    stmt::{ Stmt, FunctionStmt, ExpressionStmt },
+    expr::Expr,
+    interpreter::Interpreter,
+    environment::{ Environment, environment_value::EnvironmentValue },
+    viskum_iterator::{ IteratorSource, ViskumIterator },
+    token::{ Literal, Token },
+    error_handler::{ ViskumError, LoopControl },
+};
+
+/// A statement of the generator's body, shared with the function it's in so
+/// that frames don't copy it
+#[derive(Clone)]
+enum SharedStmt {
+    // The statement at an index of a block or of the function body
+    InBlock(Rc<[Stmt]>, usize),
+    // The branch of an if or the body of a loop
+    Single(Rc<Stmt>),
+}
+
+impl SharedStmt {
+    fn get(&self) -> &Stmt {
+        match self {
+            SharedStmt::InBlock(statements, index) => &statements[*index],
+            SharedStmt::Single(statement) => statement,
+        }
+    }
+}
+
+/// Where a paused generator is. Only the statements a yield is in get a frame,
+/// and everything else is run by the interpreter as usual
+enum Frame {
+    Statements {
+        statements: Rc<[Stmt]>,
+        next: usize,
+        environment: Rc<RefCell<Environment>>,
+    },
+    Loop {
+        statement: SharedStmt,
+        // The values a for-in loop goes through
+        iterator: Option<Rc<ViskumIterator>>,
+        environment: Rc<RefCell<Environment>>,
+        // Whether the body has run before, so the increment of a for loop runs
+        // between iterations
+        started: bool,
+    },
+}
+
+/// What calling a function with a yield in it gives. It runs the body up to
+/// the next yield every time the next value is asked for
+pub struct ViskumGenerator {
+    name: String,
+    frames: RefCell<Vec<Frame>>,
+    running: Cell<bool>,
+}
+
+impl ViskumGenerator {
+    pub fn new(function: Rc<FunctionStmt>, environment: Rc<RefCell<Environment>>) -> Self {
+        let frames = vec![Frame::Statements {
+            statements: function.body.clone(),
+            next: 0,
+            environment,
+        }];
+
+        ViskumGenerator {
+            name: function.token.lexeme.clone(),
+            frames: RefCell::new(frames),
+            running: Cell::new(false),
+        }
+    }
+
+    fn resume(
+        &self,
+        interpreter: &Interpreter,
+        frames: &mut Vec<Frame>
+    ) -> Result<Option<Literal>, ViskumError> {
+        while let Some(frame) = frames.pop() {
+            let result = match frame {
+                Frame::Statements { statements, next, environment } => {
+                    if next >= statements.len() {
+                        continue;
+                    }
+
+                    frames.push(Frame::Statements {
+                        statements: statements.clone(),
+                        next: next + 1,
+                        environment: environment.clone(),
+                    });
+
+                    self.enter(interpreter, SharedStmt::InBlock(statements, next), &environment, frames)
+                }
+                Frame::Loop { statement, iterator, environment, started } =>
+                    self.next_iteration(interpreter, statement, iterator, environment, started, frames),
+            };
+
+            match result {
+                Ok(Some(value)) => {
+                    return Ok(Some(value));
+                }
+                Ok(None) => (),
+                Err(e) => unwind(e, frames)?,
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Runs a statement, or gives it a frame if there's a yield in it
+    fn enter(
+        &self,
+        interpreter: &Interpreter,
+        statement: SharedStmt,
+        environment: &Rc<RefCell<Environment>>,
+        frames: &mut Vec<Frame>
+    ) -> Result<Option<Literal>, ViskumError> {
+        let stmt = statement.get();
+
+        if !contains_yield(stmt) {
+            interpreter.with_environment(environment.clone(), || interpreter.execute(stmt))?;
+            return Ok(None);
+        }
+
+        let in_environment = |expr: &Expr| {
+            interpreter.with_environment(environment.clone(), || interpreter.evaluate(expr))
+        };
+
+        let new_loop = |iterator, environment| Frame::Loop {
+            statement: statement.clone(),
+            iterator,
+            environment,
+            started: false,
+        };
+
+        match stmt {
+            Stmt::Yield(stmt) => {
+                let value = match &stmt.value {
+                    Some(value) => in_environment(value)?,
+                    None => Literal::Null,
+                };
+                return Ok(Some(value));
+            }
+            Stmt::Block(stmt) => {
+                frames.push(Frame::Statements {
+                    statements: stmt.statements.clone(),
+                    next: 0,
+                    environment: child_environment(environment),
+                });
+            }
+            Stmt::If(stmt) => {
+                let branch = if interpreter.is_truthy(&in_environment(&stmt.condition)?) {
+                    Some(&stmt.then_branch)
+                } else {
+                    stmt.else_branch.as_ref()
+                };
+
+                if let Some(branch) = branch {
+                    return self.enter(interpreter, SharedStmt::Single(branch.clone()), environment, frames);
+                }
+            }
+            // The only expression with a yield in it is a loop
+            Stmt::While(_) | Stmt::Expression(_) => {
+                frames.push(new_loop(None, environment.clone()));
+            }
+            Stmt::For(stmt) => {
+                // The loop variable lives in an environment of its own
+                let environment = child_environment(environment);
+
+                if let Some(initializer) = &stmt.initializer {
+                    interpreter.with_environment(environment.clone(), || interpreter.execute(initializer))?;
+                }
+
+                frames.push(new_loop(None, environment));
+            }
+            Stmt::ForIn(stmt) => {
+                let iterable = in_environment(&stmt.iterable)?;
+                let iterator = interpreter.iterator(&iterable, &stmt.token)?;
+
+                frames.push(new_loop(Some(iterator), environment.clone()));
+            }
+            // The resolver doesn't allow yields anywhere else
+            _ => {
+                interpreter.with_environment(environment.clone(), || interpreter.execute(stmt))?;
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Starts the next run of a loop's body, or ends the loop
+    fn next_iteration(
+        &self,
+        interpreter: &Interpreter,
+        statement: SharedStmt,
+        iterator: Option<Rc<ViskumIterator>>,
+        environment: Rc<RefCell<Environment>>,
+        started: bool,
+        frames: &mut Vec<Frame>
+    ) -> Result<Option<Literal>, ViskumError> {
+        let in_environment = |expr: &Expr| {
+            interpreter.with_environment(environment.clone(), || interpreter.evaluate(expr))
+        };
+
+        let (body, body_environment) = match statement.get() {
+            Stmt::While(stmt) => {
+                if !interpreter.is_truthy(&in_environment(&stmt.condition)?) {
+                    return Ok(None);
+                }
+                (stmt.body.clone(), environment.clone())
+            }
+            Stmt::For(stmt) => {
+                if let (true, Some(increment)) = (started, &stmt.increment) {
+                    in_environment(increment)?;
+                }
+                if let Some(condition) = &stmt.condition {
+                    if !interpreter.is_truthy(&in_environment(condition)?) {
+                        return Ok(None);
+                    }
+                }
+                (stmt.body.clone(), environment.clone())
+            }
+            Stmt::ForIn(stmt) => {
+                let next = match &iterator {
+                    Some(iterator) => iterator.next_value(interpreter)?,
+                    None => None,
+                };
+                let value = match next {
+                    Some(value) => value,
+                    None => {
+                        return Ok(None);
+                    }
+                };
+
+                let loop_environment = child_environment(&environment);
+                loop_environment.borrow_mut().define(&stmt.token, EnvironmentValue::new(value, false))?;
+
+                (stmt.body.clone(), loop_environment)
+            }
+            Stmt::Expression(ExpressionStmt { expression: Expr::Loop(expr) }) =>
+                (expr.body.clone(), environment.clone()),
+            // Only loops get a loop frame
+            _ => {
+                return Ok(None);
+            }
+        };
+
+        frames.push(Frame::Loop { statement, iterator, environment, started: true });
+
+        // Like any other block, the body gets a new environment every time it runs
+        self.enter(interpreter, SharedStmt::Single(body), &body_environment, frames)
+    }
+}
+
+impl IteratorSource for ViskumGenerator {
+    fn next(&self, interpreter: &Interpreter) -> Result<Option<Literal>, ViskumError> {
+        if self.running.replace(true) {
+            return Err(
+                ViskumError::new(
+                    format!("Generator {} can't resume itself while it's running", self.name).as_str(),
+                    Token::invalid(None)
+                )
+            );
+        }
+
+        let mut frames = self.frames.take();
+        let result = self.resume(interpreter, &mut frames);
+
+        self.running.set(false);
+
+        match result {
+            Ok(Some(value)) => {
+                self.frames.replace(frames);
+                Ok(Some(value))
+            }
+            // A return ends the generator, and a call in tail position
+            // still has to be performed
+            Err(e) if e.get_abort_value().is_some() => Ok(None),
+            Err(e) =>
+                match e.into_tail_call() {
+                    Ok((callable, arguments, call_site)) => {
+                        callable.func
+                            .call(interpreter, &arguments)
+                            .map_err(|e| e.with_call_site(&call_site))?;
+                        Ok(None)
+                    }
+                    Err(e) => Err(e),
+                }
+            Ok(None) => Ok(None),
+        }
+    }
+
+    fn to_string(&self) -> String {
+        format!("<generator {}>", self.name)
+    }
+}
+
+fn child_environment(environment: &Rc<RefCell<Environment>>) -> Rc<RefCell<Environment>> {
+    Rc::new(RefCell::new(Environment::new_with_enclosing(environment.clone())))
+}
+
+/// Passes a break or continue on to the loop it's for, leaving the frames
+/// inside of that loop. Any other error leaves the generator
+fn unwind(mut error: ViskumError, frames: &mut Vec<Frame>) -> Result<(), ViskumError> {
+    while let Some(frame) = frames.pop() {
+        let label = match &frame {
+            Frame::Loop { statement, .. } => loop_label(statement.get()),
+            Frame::Statements { .. } => {
+                continue;
+            }
+        };
+
+        match error.into_loop_control(label) {
+            Ok(LoopControl::Break(_)) => {
+                return Ok(());
+            }
+            Ok(LoopControl::Continue) => {
+                frames.push(frame);
+                return Ok(());
+            }
+            Err(e) => {
+                error = e;
+            }
+        }
+    }
+
+    Err(error)
+}
+
+fn loop_label(statement: &Stmt) -> Option<&Token> {
+    match statement {
+        Stmt::While(stmt) => stmt.label.as_ref(),
+        Stmt::For(stmt) => stmt.label.as_ref(),
+        Stmt::ForIn(stmt) => stmt.label.as_ref(),
+        Stmt::Expression(ExpressionStmt { expression: Expr::Loop(expr) }) => expr.label.as_ref(),
+        _ => None,
+    }
+}
+
+/// Whether the statement has a yield in a place the generator can pause in
+pub fn contains_yield(statement: &Stmt) -> bool {
+    match statement {
+        Stmt::Yield(_) => true,
+        Stmt::Block(stmt) => stmt.statements.iter().any(contains_yield),
+        Stmt::If(stmt) =>
+            contains_yield(&stmt.then_branch) ||
+                stmt.else_branch.as_ref().is_some_and(|branch| contains_yield(branch)),
+        Stmt::While(stmt) => contains_yield(&stmt.body),
+        Stmt::For(stmt) => contains_yield(&stmt.body),
+        Stmt::ForIn(stmt) => contains_yield(&stmt.body),
+        Stmt::Expression(ExpressionStmt { expression: Expr::Loop(loop_expr) }) =>
+            contains_yield(&loop_expr.body),
+        _ => false,
+    }
+}
@@ -0,0 +1,67 @@
+use std::{ rc::Rc, cell::RefCell, collections::HashMap };
+
+use crate::{
+    viskum_class::ViskumClass,
+    viskum_callable::{ Callable, ViskumCallable },
+    token::{ Literal, Token },
+    error_handler::ViskumError,
+};
+
+pub struct ViskumInstance {
+    class: ViskumClass,
+    fields: HashMap<String, Literal>,
+}
+
+impl ViskumInstance {
+    pub fn new(class: ViskumClass) -> Self {
+        ViskumInstance { class, fields: HashMap::new() }
+    }
+
+    pub fn class_name(&self) -> &str {
+        &self.class.name
+    }
+
+    pub fn get(instance: &Rc<RefCell<ViskumInstance>>, token: &Token) -> Result<Literal, ViskumError> {
+        if let Some(value) = instance.borrow().fields.get(&token.lexeme) {
+            return Ok(value.clone());
+        }
+
+        if let Some(method) = instance.borrow().class.find_method(&token.lexeme) {
+            let bound_method = method.bind(Literal::Instance(instance.clone()));
+            let arity = bound_method.arity();
+
+            return Ok(Literal::Func(Callable { func: Rc::new(bound_method), arity }));
+        }
+
+        Err(
+            ViskumError::new(
+                format!(
+                    "Undefined property '{}' on {} instance",
+                    token.lexeme,
+                    instance.borrow().class_name()
+                ).as_str(),
+                token.clone()
+            )
+        )
+    }
+
+    pub fn has_method(&self, name: &str) -> bool {
+        self.class.find_method(name).is_some()
+    }
+
+    pub fn set(&mut self, token: &Token, value: Literal) {
+        self.fields.insert(token.lexeme.clone(), value);
+    }
+}
+
+impl PartialEq for ViskumInstance {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl std::fmt::Debug for ViskumInstance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{} instance>", self.class.name)
+    }
+}
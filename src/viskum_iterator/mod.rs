@@ -0,0 +1,202 @@
+use std::{ rc::Rc, cell::RefCell };
+
+use crate::{
+    viskum_callable::{ Callable, ViskumCallable, Arity },
+    viskum_instance::ViskumInstance,
+    interpreter::Interpreter,
+    token::{ Literal, Token, TokenType },
+    error_handler::ViskumError,
+};
+
+/// Produces the values of an iterator one at a time. Ok(None) means there are
+/// no more values
+pub trait IteratorSource {
+    fn next(&self, interpreter: &Interpreter) -> Result<Option<Literal>, ViskumError>;
+    fn to_string(&self) -> String;
+}
+
+/// The value for-in loops go through. It has the methods next() and
+/// has_next(), and is made by iter(), generator functions, and for-in loops
+/// over other values
+pub struct ViskumIterator {
+    source: Box<dyn IteratorSource>,
+    // has_next() has to produce the next value to know if there is one
+    peeked: RefCell<Option<Option<Literal>>>,
+}
+
+impl ViskumIterator {
+    pub fn new_literal(source: impl IteratorSource + 'static) -> Literal {
+        Literal::Iterator(Rc::new(ViskumIterator::from_source(source)))
+    }
+
+    pub fn from_source(source: impl IteratorSource + 'static) -> Self {
+        ViskumIterator { source: Box::new(source), peeked: RefCell::new(None) }
+    }
+
+    pub fn next_value(&self, interpreter: &Interpreter) -> Result<Option<Literal>, ViskumError> {
+        // Taken before producing a value, since the source can use this iterator
+        let peeked = self.peeked.take();
+
+        match peeked {
+            Some(value) => Ok(value),
+            None => self.source.next(interpreter),
+        }
+    }
+
+    pub fn has_next(&self, interpreter: &Interpreter) -> Result<bool, ViskumError> {
+        let value = self.next_value(interpreter)?;
+        let has_next = value.is_some();
+
+        self.peeked.replace(Some(value));
+
+        Ok(has_next)
+    }
+
+    pub fn get(iterator: &Rc<ViskumIterator>, token: &Token) -> Result<Literal, ViskumError> {
+        let method = match token.lexeme.as_str() {
+            "next" => IteratorMethodKind::Next,
+            "has_next" => IteratorMethodKind::HasNext,
+            _ => {
+                return Err(
+                    ViskumError::new(
+                        format!("Iterator has no method '{}'", token.lexeme).as_str(),
+                        token.clone()
+                    )
+                );
+            }
+        };
+
+        Ok(
+            Literal::Func(Callable {
+                func: Rc::new(IteratorMethod { iterator: iterator.clone(), method }),
+                arity: Arity::exactly(0),
+            })
+        )
+    }
+}
+
+impl PartialEq for ViskumIterator {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl std::fmt::Debug for ViskumIterator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source.to_string())
+    }
+}
+
+enum IteratorMethodKind {
+    Next,
+    HasNext,
+}
+
+/// it.next() or it.has_next()
+struct IteratorMethod {
+    iterator: Rc<ViskumIterator>,
+    method: IteratorMethodKind,
+}
+
+impl ViskumCallable for IteratorMethod {
+    fn call(
+        &self,
+        interpreter: &Interpreter,
+        _arguments: &Vec<Literal>
+    ) -> Result<Literal, ViskumError> {
+        match self.method {
+            IteratorMethodKind::Next =>
+                match self.iterator.next_value(interpreter)? {
+                    Some(value) => Ok(value),
+                    None =>
+                        Err(
+                            ViskumError::new(
+                                "The iterator has no more values",
+                                Token::invalid(None)
+                            )
+                        ),
+                }
+            IteratorMethodKind::HasNext => Ok(Literal::Bool(self.iterator.has_next(interpreter)?)),
+        }
+    }
+
+    fn arity(&self) -> Arity {
+        Arity::exactly(0)
+    }
+
+    fn to_string(&self) -> String {
+        match self.method {
+            IteratorMethodKind::Next => "<native fn next>".to_string(),
+            IteratorMethodKind::HasNext => "<native fn has_next>".to_string(),
+        }
+    }
+}
+
+/// The values of strings, lists, tuples, maps and ranges
+pub struct NativeSource {
+    values: RefCell<Box<dyn Iterator<Item = Literal>>>,
+}
+
+impl NativeSource {
+    pub fn new(values: Box<dyn Iterator<Item = Literal>>) -> Self {
+        NativeSource { values: RefCell::new(values) }
+    }
+}
+
+impl IteratorSource for NativeSource {
+    fn next(&self, _interpreter: &Interpreter) -> Result<Option<Literal>, ViskumError> {
+        Ok(self.values.borrow_mut().next())
+    }
+
+    fn to_string(&self) -> String {
+        "<iterator>".to_string()
+    }
+}
+
+/// An instance of a user class with has_next() and next() methods
+pub struct InstanceSource {
+    instance: Rc<RefCell<ViskumInstance>>,
+    token: Token,
+}
+
+impl InstanceSource {
+    pub fn new(instance: Rc<RefCell<ViskumInstance>>, token: &Token) -> Self {
+        InstanceSource { instance, token: token.clone() }
+    }
+
+    fn call_method(&self, interpreter: &Interpreter, name: &str) -> Result<Literal, ViskumError> {
+        let token = Token::new(
+            TokenType::Identifier,
+            name.to_string(),
+            None,
+            self.token.line,
+            self.token.file.clone()
+        );
+
+        match ViskumInstance::get(&self.instance, &token)? {
+            Literal::Func(callable) =>
+                callable.func.call(interpreter, &Vec::new()).map_err(|e| e.with_call_site(&token)),
+            value =>
+                Err(
+                    ViskumError::new(
+                        format!("Expected '{}' to be a method, but it's a {}", name, value.to_type_string()).as_str(),
+                        token
+                    )
+                ),
+        }
+    }
+}
+
+impl IteratorSource for InstanceSource {
+    fn next(&self, interpreter: &Interpreter) -> Result<Option<Literal>, ViskumError> {
+        if !interpreter.is_truthy(&self.call_method(interpreter, "has_next")?) {
+            return Ok(None);
+        }
+
+        Ok(Some(self.call_method(interpreter, "next")?))
+    }
+
+    fn to_string(&self) -> String {
+        format!("<iterator over {:?}>", self.instance.borrow())
+    }
+}
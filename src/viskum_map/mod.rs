@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use crate::{ token::{ Literal, Token }, error_handler::ViskumError };
+
+/// The hashable form of the values that can be used as map keys
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum MapKey {
+    Int(i64),
+    Float(u64),
+    Str(String),
+    Bool(bool),
+    Null,
+    Tuple(Vec<MapKey>),
+}
+
+impl MapKey {
+    fn new(key: &Literal, token: &Token) -> Result<MapKey, ViskumError> {
+        match key {
+            Literal::Num(x) if x.is_nan() =>
+                Err(ViskumError::new("NaN cannot be used as a map key", token.clone())),
+            Literal::Int(x) => Ok(MapKey::Int(*x)),
+            // 2.0 == 2, so whole floats have to be the same key as the int
+            Literal::Num(x) if x.fract() == 0.0 && *x >= (i64::MIN as f64) && *x < (i64::MAX as f64) =>
+                Ok(MapKey::Int(*x as i64)),
+            Literal::Num(x) => Ok(MapKey::Float(x.to_bits())),
+            Literal::Str(str) => Ok(MapKey::Str(str.clone())),
+            Literal::Bool(b) => Ok(MapKey::Bool(*b)),
+            Literal::Null => Ok(MapKey::Null),
+            Literal::Tuple(tuple) =>
+                Ok(
+                    MapKey::Tuple(
+                        tuple
+                            .iter()
+                            .map(|element| MapKey::new(element, token))
+                            .collect::<Result<_, _>>()?
+                    )
+                ),
+            _ =>
+                Err(
+                    ViskumError::new(
+                        format!(
+                            "A {} cannot be used as a map key: Keys must be numbers, strings, bools, null or tuples of those",
+                            key.to_type_string()
+                        ).as_str(),
+                        token.clone()
+                    )
+                ),
+        }
+    }
+}
+
+/// A map that remembers the order its keys were first inserted in
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ViskumMap {
+    indices: HashMap<MapKey, usize>,
+    entries: Vec<(Literal, Literal)>,
+}
+
+impl ViskumMap {
+    pub fn new() -> Self {
+        ViskumMap { indices: HashMap::new(), entries: Vec::new() }
+    }
+
+    pub fn get(&self, key: &Literal, token: &Token) -> Result<Option<Literal>, ViskumError> {
+        let map_key = MapKey::new(key, token)?;
+
+        Ok(self.indices.get(&map_key).map(|i| self.entries[*i].1.clone()))
+    }
+
+    pub fn insert(&mut self, key: Literal, value: Literal, token: &Token) -> Result<(), ViskumError> {
+        let map_key = MapKey::new(&key, token)?;
+
+        match self.indices.get(&map_key) {
+            Some(i) => {
+                self.entries[*i].1 = value;
+            }
+            None => {
+                self.indices.insert(map_key, self.entries.len());
+                self.entries.push((key, value));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn entries(&self) -> &[(Literal, Literal)] {
+        &self.entries
+    }
+
+    pub fn keys(&self) -> Vec<Literal> {
+        self.entries
+            .iter()
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
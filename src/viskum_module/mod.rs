@@ -0,0 +1,146 @@
+use std::{ rc::Rc, cell::RefCell, collections::HashMap, fs, path::{ Path, PathBuf } };
+
+use crate::{
+    environment::Environment,
+    error_handler::{ ErrorHandler, ViskumError },
+    interpreter::Interpreter,
+    run::parse_source,
+    token::{ Literal, Token },
+};
+
+/// An imported file. Its top-level bindings are read with '.'
+pub struct ViskumModule {
+    pub name: String,
+    environment: Rc<RefCell<Environment>>,
+}
+
+impl ViskumModule {
+    /// Only the names the module declares itself are members, and not the
+    /// native functions
+    pub fn get(&self, token: &Token) -> Result<Literal, ViskumError> {
+        self.environment
+            .borrow()
+            .get_local(&token.lexeme)
+            .ok_or_else(|| {
+                ViskumError::new(
+                    format!("Module '{}' has no member '{}'", self.name, token.lexeme).as_str(),
+                    token.clone()
+                )
+            })
+    }
+}
+
+impl PartialEq for ViskumModule {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+impl std::fmt::Debug for ViskumModule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<module {}>", self.name)
+    }
+}
+
+/// Runs every imported file once, no matter how many files import it
+pub struct ModuleLoader {
+    modules: RefCell<HashMap<PathBuf, Rc<ViskumModule>>>,
+    // The files that are being run right now, to detect import cycles
+    loading: RefCell<Vec<(PathBuf, String)>>,
+}
+
+impl ModuleLoader {
+    pub fn new() -> Self {
+        ModuleLoader { modules: RefCell::new(HashMap::new()), loading: RefCell::new(Vec::new()) }
+    }
+
+    /// The file that's run directly counts as being loaded, so a module that
+    /// imports it is a cycle
+    pub fn set_main_file(&self, file: &str) {
+        if let Ok(path) = fs::canonicalize(file) {
+            self.loading.borrow_mut().push((path, file.to_string()));
+        }
+    }
+
+    /// Imports the file at `path`, relative to the file the import is in
+    pub fn import(
+        &self,
+        path: &str,
+        name: &str,
+        token: &Token
+    ) -> Result<Rc<ViskumModule>, ViskumError> {
+        let file = Path::new(&*token.file)
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(path)
+            .to_string_lossy()
+            .to_string();
+
+        let canonical_path = fs::canonicalize(&file).map_err(|e| {
+            ViskumError::new(format!("Cannot import '{}': {}", file, e).as_str(), token.clone())
+        })?;
+
+        if let Some(module) = self.modules.borrow().get(&canonical_path) {
+            return Ok(module.clone());
+        }
+
+        if let Some(error) = self.cycle_error(&canonical_path, &file, token) {
+            return Err(error);
+        }
+
+        self.loading.borrow_mut().push((canonical_path.clone(), file.clone()));
+        let result = self.run_module(&file, name, token);
+        self.loading.borrow_mut().pop();
+
+        let module = Rc::new(result?);
+        self.modules.borrow_mut().insert(canonical_path, module.clone());
+
+        Ok(module)
+    }
+
+    fn cycle_error(&self, path: &Path, file: &str, token: &Token) -> Option<ViskumError> {
+        let loading = self.loading.borrow();
+        let start = loading.iter().position(|(loading_path, _)| loading_path == path)?;
+
+        let cycle: Vec<&str> = loading[start..]
+            .iter()
+            .map(|(_, loading_file)| loading_file.as_str())
+            .chain(std::iter::once(file))
+            .collect();
+
+        Some(
+            ViskumError::new(
+                format!("Import cycle: {}", cycle.join(" -> ")).as_str(),
+                token.clone()
+            )
+        )
+    }
+
+    fn run_module(&self, file: &str, name: &str, token: &Token) -> Result<ViskumModule, ViskumError> {
+        let source = fs::read_to_string(file).map_err(|e| {
+            ViskumError::new(format!("Cannot import '{}': {}", file, e).as_str(), token.clone())
+        })?;
+
+        let error_handler = RefCell::new(ErrorHandler::new(file));
+
+        let statements = match parse_source(&source, file, &error_handler) {
+            Some(statements) => statements,
+            None => {
+                error_handler.borrow().print_errors();
+
+                return Err(
+                    ViskumError::new(
+                        format!("Cannot import '{}' because it contains errors", file).as_str(),
+                        token.clone()
+                    )
+                );
+            }
+        };
+
+        let environment = RefCell::new(Rc::new(RefCell::new(Environment::new())));
+
+        Interpreter::new(&error_handler, &environment, self).execute_statements(&statements)?;
+
+        Ok(ViskumModule { name: name.to_string(), environment: environment.into_inner() })
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{
+    viskum_callable::{ Callable, ViskumCallable, Arity },
+    interpreter::Interpreter,
+    token::{ Literal, Token },
+    error_handler::ViskumError,
+};
+
+/// A function with some of its arguments given beforehand, made by a call with
+/// '_' placeholders like add(_, 1). The arguments it's called with fill the
+/// placeholders in order, and any left over come after the given ones
+pub struct ViskumPartial {
+    callable: Callable,
+    // None where there's a placeholder
+    arguments: Vec<Option<Literal>>,
+    named: Vec<(Token, Literal)>,
+}
+
+impl ViskumPartial {
+    pub fn new(
+        callable: Callable,
+        arguments: Vec<Option<Literal>>,
+        named: Vec<(Token, Literal)>
+    ) -> Self {
+        ViskumPartial { callable, arguments, named }
+    }
+
+    fn placeholder_count(&self) -> usize {
+        self.arguments
+            .iter()
+            .filter(|argument| argument.is_none())
+            .count()
+    }
+}
+
+impl ViskumCallable for ViskumPartial {
+    fn call(&self, interpreter: &Interpreter, arguments: &Vec<Literal>) -> Result<Literal, ViskumError> {
+        self.call_named(interpreter, arguments, &[])
+    }
+
+    fn call_named(
+        &self,
+        interpreter: &Interpreter,
+        arguments: &Vec<Literal>,
+        named: &[(Token, Literal)]
+    ) -> Result<Literal, ViskumError> {
+        let mut remaining = arguments.iter().cloned();
+
+        let mut all_arguments = self.arguments
+            .iter()
+            .map(|argument| {
+                argument
+                    .clone()
+                    .or_else(|| remaining.next())
+                    .ok_or_else(|| {
+                        ViskumError::new(
+                            format!(
+                                "{} expected {} arguments but received {}",
+                                self.to_string(),
+                                self.arity(),
+                                arguments.len()
+                            ).as_str(),
+                            Token::invalid(None)
+                        )
+                    })
+            })
+            .collect::<Result<Vec<Literal>, ViskumError>>()?;
+        all_arguments.extend(remaining);
+
+        let all_named: Vec<(Token, Literal)> = self.named.iter().chain(named).cloned().collect();
+
+        self.callable.func.call_named(interpreter, &all_arguments, &all_named)
+    }
+
+    /// The placeholders have to be filled, and the parameters that weren't
+    /// given an argument can be
+    fn arity(&self) -> Arity {
+        let placeholders = self.placeholder_count();
+        let given = self.arguments.len() - placeholders + self.named.len();
+        let arity = self.callable.arity;
+
+        Arity {
+            min: placeholders.max(arity.min.saturating_sub(given)),
+            max: arity.max.map(|max| max.saturating_sub(given)),
+        }
+    }
+
+    fn to_string(&self) -> String {
+        let name = self.callable.func.to_string();
+
+        format!("<partial {}>", name.trim_start_matches('<').trim_end_matches('>'))
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt;
+
+/// A range of ints like 0..10 or 0..=10 step 2. The values are only made
+/// when the range is iterated, so a range takes the same space no matter how
+/// long it is
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViskumRange {
+    pub start: i64,
+    pub end: i64,
+    pub step: i64,
+    pub inclusive: bool,
+}
+
+impl ViskumRange {
+    /// The step can't be 0
+    pub fn new(start: i64, end: i64, step: i64, inclusive: bool) -> Self {
+        ViskumRange { start, end, step, inclusive }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let in_bounds = if self.step > 0 {
+            value >= self.start && (value < self.end || (self.inclusive && value == self.end))
+        } else {
+            value <= self.start && (value > self.end || (self.inclusive && value == self.end))
+        };
+
+        in_bounds && ((value as i128) - (self.start as i128)) % (self.step as i128) == 0
+    }
+
+    pub fn len(&self) -> usize {
+        let distance = ((self.end as i128) - (self.start as i128)) * (self.step.signum() as i128);
+        let distance = if self.inclusive { distance + 1 } else { distance };
+
+        if distance <= 0 {
+            return 0;
+        }
+
+        let step = (self.step as i128).abs();
+        // Only the inclusive range of every int has more values than fit
+        usize::try_from((distance + step - 1) / step).unwrap_or(usize::MAX)
+    }
+
+    pub fn iter(&self) -> RangeIter {
+        RangeIter { range: self.clone(), next: Some(self.start) }
+    }
+}
+
+impl fmt::Display for ViskumRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}{}", self.start, if self.inclusive { "..=" } else { ".." }, self.end)?;
+
+        if self.step != 1 {
+            write!(f, " step {}", self.step)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct RangeIter {
+    range: ViskumRange,
+    next: Option<i64>,
+}
+
+impl Iterator for RangeIter {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        let value = self.next.filter(|value| self.range.contains(*value))?;
+
+        // Stops instead of overflowing at the end of the ints
+        self.next = value.checked_add(self.range.step);
+
+        Some(value)
+    }
+}
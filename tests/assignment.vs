@@ -0,0 +1,72 @@
+// Assignments are expressions that evaluate to the assigned value
+let mut a = 0
+let mut b = 0
+a = b = 5
+print a // 5
+print b // 5
+
+let mut c = 1
+print c += 2 // 3
+print (c *= 2) + 1 // 7
+
+// So they can be used in conditions
+let items = ["first", "second"]
+let mut position = 0
+fn next() {
+    return position < len(items) ? items[position++] : null
+}
+
+let mut item = null
+while (item = next()) != null {
+    print item // first, second
+}
+
+// Postfix ++ and -- evaluate to the value before, prefix ones to the value after
+let mut n = 5
+print n++ // 5
+print n // 6
+print ++n // 7
+print n-- // 7
+print --n // 5
+
+// They work on properties and indices too
+class Counter {
+    fn init() {
+        this.count = 0
+    }
+}
+let counter = new Counter()
+print counter.count++ // 0
+print ++counter.count // 2
+
+let list = [1, 2, 3]
+print list[0]++ // 1
+print --list[2] // 2
+print list // [2, 2, 2]
+
+let map = ["x": 1]
+map["x"] = map["y"] = 10
+print map // ["x": 10, "y": 10]
+
+// Parentheses around the target are allowed
+(a) = 1
+print a // 1
+
+// a + b = 1; // Error: Invalid assignment target at '='
+// Variables declared without 'mut' can't be assigned to
+let fixed = 1
+try {
+    fixed = 2
+} catch e {
+    print e.message // Cannot assign to immutable variable 'fixed' declared on line 57. Declare it with 'let mut' to make it mutable
+}
+
+// That includes the variable of a for loop
+try {
+    for let i = 0; i < 2; { i = 2 }
+} catch e {
+    print e.message // Cannot assign to immutable variable 'i' declared on line 66. Declare it with 'let mut' to make it mutable
+}
+
+// and an increment assigning to it is an error before the program runs
+// for let i = 0; i < 2; i++ {} // Error: Cannot assign to immutable variable 'i' declared on line 73. Declare it with 'let mut' to make it mutable
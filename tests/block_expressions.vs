@@ -0,0 +1,83 @@
+// Blocks and if as expressions
+
+let x = 5
+let size = if x > 3 { "big" } else { "small" }
+print size // big
+
+// else if chains, and the value is null without an else
+let sign = if x < 0 { -1 } else if x == 0 { 0 } else { 1 }
+print sign // 1
+print if x > 10 { "huge" } // null
+
+// The last expression of a block is its value
+let y = {
+    let t = x * 2
+    t + 1
+}
+print y // 11
+
+// ...unless it ends with an explicit ';'
+let z = {
+    x * 2;
+}
+print z // null
+
+print { 1; 2; 3 } // 3
+print {} // null
+
+// Statements in a block run before its value
+let total = {
+    let mut sum = 0
+    for i in 1..=4 {
+        sum += i
+    }
+    sum
+}
+print total // 10
+
+// The names declared in a block don't leak out of it
+let t = "outer"
+let inner = {
+    let t = "inner"
+    t
+}
+print inner // inner
+print t // outer
+
+// An if at the end of a block gives the block its value
+fn classify(n) {
+    return {
+        let even = n % 2 == 0
+        if even { "even" } else { "odd" }
+    }
+}
+print classify(3) // odd
+print classify(4) // even
+
+// Blocks in match arms have values too
+let doubled = match 4 {
+    0 => { 0 }
+    n => {
+        let d = n * 2
+        d
+    }
+}
+print doubled // 8
+
+match x {
+    5 => {
+        print "five" // five
+    }
+    _ => {
+        print "other"
+    }
+}
+
+let greeting = if x > 3 {
+    print "deciding" // deciding
+    "hello"
+}
+else {
+    "bye"
+}
+print greeting // hello
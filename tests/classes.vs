@@ -0,0 +1,23 @@
+class Point {
+  fn init(x, y) {
+    this.x = x;
+    this.y = y;
+  }
+
+  fn add(other) {
+    return new Point(this.x + other.x, this.y + other.y);
+  }
+
+  fn to_string() {
+    return "(" + this.x + ", " + this.y + ")";
+  }
+}
+
+let a = new Point(1, 2);
+let b = new Point(3, 4);
+let c = a.add(b);
+
+print c.to_string(); // (4, 6)
+
+c.x += 10;
+print c.x; // 14
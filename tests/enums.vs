@@ -0,0 +1,43 @@
+enum Shape {
+    Circle(radius),
+    Rect(width, height),
+    Empty,
+}
+
+fn area(shape) {
+    return match shape {
+        Shape.Circle(r) => 3 * r * r,
+        Shape.Rect(w, h) if w == h => "a square of {w * h}",
+        Shape.Rect(w, h) => w * h,
+        Shape.Empty => 0,
+    };
+}
+
+for shape in [Shape.Circle(2), Shape.Rect(3, 4), Shape.Rect(2, 2), Shape.Empty] {
+    print "{shape} has the tag {tag_of(shape)} and area {area(shape)}";
+}
+
+let circle = Shape.Circle(5);
+print circle.radius;
+print circle == Shape.Circle(5);
+print circle == Shape.Circle(6);
+
+enum Light {
+    Red,
+    Yellow,
+    Green,
+}
+
+fn next(light) {
+    return match light {
+        Light.Red => Light.Green,
+        Light.Green => Light.Yellow,
+        Light.Yellow => Light.Red,
+    };
+}
+
+let mut light = Light.Red;
+for i in [1, 2, 3] {
+    light = next(light);
+    print light;
+}
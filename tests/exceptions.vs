@@ -0,0 +1,145 @@
+// Thrown values are caught as errors with a message, line and value
+try {
+    throw "boom";
+} catch e {
+    print e; // <error: boom>
+    print e.message; // boom
+    print e.line; // 3
+    print e.value; // boom
+}
+
+try {
+    throw [1, 2];
+} catch e {
+    print e.value[1]; // 2
+}
+
+// Errors from the interpreter can be caught too
+try {
+    print undefined_variable;
+} catch e {
+    print e.message; // Undefined variable 'undefined_variable'
+    print e.value; // null
+}
+
+try {
+    let x = 1 - "a";
+} catch e {
+    print e.message; // '-' is not defined for int and string
+}
+
+fn add(a, b) {
+    return a + b;
+}
+
+try {
+    add(1);
+} catch e {
+    print e.message; // Expected 2 arguments but received 1
+}
+
+// Errors thrown inside of functions unwind through them
+fn check(n) {
+    if n < 0 {
+        throw "negative";
+    }
+    return n;
+}
+
+fn safe_check(n) {
+    try {
+        return check(n);
+    } catch e {
+        return e.message;
+    }
+}
+
+print safe_check(1); // 1
+print safe_check(-1); // negative
+
+// finally runs no matter how the try block is left
+fn with_finally() {
+    try {
+        return "try";
+    } finally {
+        print "finally";
+    }
+}
+print with_finally(); // finally, then try
+
+for let mut i = 0; i < 3; i++ {
+    try {
+        if i == 1 {
+            continue;
+        }
+        print i; // 0, 2
+    } finally {
+        print "after " + i; // after 0, after 1, after 2
+    }
+}
+
+try {
+    try {
+        throw "inner";
+    } finally {
+        print "inner finally";
+    }
+} catch e {
+    print "caught " + e.message; // caught inner
+}
+
+// Rethrowing keeps the original error
+try {
+    try {
+        throw "first";
+    } catch e {
+        throw e;
+    }
+} catch e {
+    print e.line; // 94
+}
+
+// Errors in catch are not caught by the same try
+try {
+    try {
+        throw "a";
+    } catch e {
+        throw "b";
+    }
+} catch e {
+    print e.message; // b
+}
+
+// A call returned from catch runs before finally
+fn after_catch() {
+    print "after catch";
+    return "done";
+}
+
+fn catch_then_finally() {
+    try {
+        throw "x";
+    } catch e {
+        return after_catch();
+    } finally {
+        print "finally";
+    }
+}
+print catch_then_finally(); // after catch, finally, then done
+
+// A native function's error is on the line of the tail call that reached it
+fn length_of(value) {
+    return len(value);
+}
+
+fn tail_calls_length() {
+    return length_of(5);
+}
+
+try {
+    tail_calls_length();
+} catch e {
+    print e.line; // 132
+}
+
+// throw "uncaught"; // Error: Uncaught error: uncaught
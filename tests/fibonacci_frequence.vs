@@ -1,7 +1,7 @@
-let a = 0;
-let temp = 0;
+let mut a = 0;
+let mut temp = 0;
 
-let b = 1;
+let mut b = 1;
 while a < 10000 {
   b = temp + b;
 
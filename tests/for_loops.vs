@@ -0,0 +1,14 @@
+let mut total = 0;
+
+for let mut i = 1; i <= 10; i++ {
+  if i == 5 {
+    continue;
+  }
+  total += i;
+}
+
+print total; // 50
+
+for ch in "viskum" {
+  print ch;
+}
@@ -0,0 +1,161 @@
+// Generator functions and the iteration protocol
+
+// A function with a yield in it gives an iterator instead of running its body
+fn count_to(n) {
+    let mut i = 1
+    while i <= n {
+        yield i
+        i += 1
+    }
+}
+
+for x in count_to(3) {
+    print x // 1, 2, then 3
+}
+
+// The body only runs up to the next yield
+fn noisy() {
+    print "start"
+    yield 1
+    print "middle"
+    yield 2
+    print "end"
+}
+
+let it = noisy()
+print it // <generator noisy>
+print it.next() // start, then 1
+print it.has_next() // middle, then true
+print it.next() // 2
+print it.has_next() // end, then false
+
+// Generators can run forever, since values are only made when asked for
+fn naturals() {
+    let mut n = 0
+    loop {
+        yield n
+        n += 1
+    }
+}
+
+for n in naturals() {
+    if n > 3 {
+        break
+    }
+    print n // 0, 1, 2, then 3
+}
+
+// yield works in for loops, nested blocks and if statements, and return ends
+// the generator
+fn evens(limit) {
+    for i in 0..100 {
+        if i >= limit {
+            return
+        }
+        if i % 2 == 0 {
+            yield i
+        } else {
+            continue
+        }
+    }
+}
+
+let mut found = []
+for x in evens(7) {
+    push(found, x)
+}
+print found // [0, 2, 4, 6]
+
+fn pairs(xs) {
+    'outer: for let mut i = 0; i < len(xs); i++ {
+        for y in xs {
+            if y == xs[i] {
+                continue 'outer
+            }
+            yield (xs[i], y)
+        }
+    }
+}
+
+for pair in pairs(["a", "b", "c"]) {
+    print pair // ("b", "a"), ("c", "a"), then ("c", "b")
+}
+
+// Lambdas can be generators too
+let squares = fn(xs) {
+    for x in xs {
+        yield x * x
+    }
+}
+
+let mut total = 0
+for square in squares([1, 2, 3]) {
+    total += square
+}
+print total // 14
+
+// iter() gives the iterator a for-in loop would use
+let letters = iter("hey")
+print letters.next() // h
+for letter in letters {
+    print letter // e, then y
+}
+
+let r = iter(1..3)
+print r.next() + r.next() // 3
+print r.has_next() // false
+
+// Classes can be iterated with has_next() and next() methods
+class Countdown {
+    fn init(from) {
+        this.n = from
+    }
+
+    fn has_next() {
+        return this.n > 0
+    }
+
+    fn next() {
+        this.n -= 1
+        return this.n + 1
+    }
+}
+
+for n in new Countdown(3) {
+    print n // 3, 2, then 1
+}
+
+// or with an iter() method giving something that can be iterated
+class Bag {
+    fn init() {
+        this.items = ["x", "y"]
+    }
+
+    fn iter() {
+        for item in this.items {
+            yield item
+        }
+    }
+}
+
+for item in new Bag() {
+    print item // x, then y
+}
+
+// Errors
+let done = count_to(0)
+try {
+    done.next()
+} catch e {
+    print e.message // The iterator has no more values
+}
+
+try {
+    for x in 5 {}
+} catch e {
+    print e.message // Cannot iterate over a int
+}
+
+// yield 1 // Error: Cannot yield from top-level code
+// fn f() { let x = { yield 1 } } // Error: Cannot yield inside of an expression or a try block
+// fn f() { try { yield 1 } catch e {} } // Error: Cannot yield inside of an expression or a try block
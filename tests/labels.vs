@@ -0,0 +1,69 @@
+// Labeled break and continue, and loops with values
+
+// break 'label leaves the loop with that label, not just the innermost one
+'outer: for i in 0..3 {
+    for j in 0..3 {
+        if j == 2 {
+            continue 'outer
+        }
+        if i == 2 {
+            break 'outer
+        }
+        print "{i} {j}" // 0 0, 0 1, 1 0, then 1 1
+    }
+}
+
+let mut n = 0
+'rows: while n < 10 {
+    n += 1
+    loop {
+        if n % 2 == 0 {
+            continue 'rows
+        }
+        break
+    }
+    print n // 1, 3, 5, 7, then 9
+}
+
+// break with a value makes loop an expression
+let mut i = 0
+let first_square = loop {
+    i += 1
+    if i * i > 50 {
+        break i * i
+    }
+}
+print first_square // 64
+
+let nothing = loop {
+    break
+}
+print nothing // null
+
+// A labeled loop can be broken out of with a value from an inner loop
+let found = 'search: loop {
+    for x in 1..10 {
+        for y in 1..10 {
+            if x * y == 42 {
+                break 'search (x, y)
+            }
+        }
+    }
+    break 'search null
+}
+print found // (6, 7)
+
+// A loop at the end of a block gives the block its value
+let count = {
+    let mut k = 0
+    loop {
+        k += 3
+        if k > 10 { break k }
+    }
+}
+print count // 12
+
+// break // Error: Cannot use 'break' outside of a loop
+// 'a: loop { break 'b } // Error: Unknown loop label 'b
+// while true { break 5 } // Error: Cannot break with a value out of a while or for loop: Only 'loop' has a value
+// for x in 0..1 { fn f() { break } } // Error: Cannot use 'break' outside of a loop
@@ -0,0 +1,31 @@
+fn apply(f, x) {
+    return f(x);
+}
+
+print apply(fn(x) => x * 2, 21);
+print apply(fn(x) { let y = x + 1; return y * y; }, 2);
+
+fn adder(n) {
+    return fn(x) => x + n;
+}
+
+let add5 = adder(5);
+print add5(10);
+
+fn counter() {
+    let mut count = 0;
+    return fn() {
+        count += 1;
+        return count;
+    };
+}
+
+let next = counter();
+next();
+next();
+print next();
+
+let operations = [fn(a, b) => a + b, fn(a, b) => a * b];
+print operations[1](3, 4);
+
+fn() { print "called immediately"; }();
@@ -0,0 +1,26 @@
+let numbers = [3, 1, 4, 1, 5];
+let alias = numbers;
+
+alias[0] = 30;
+numbers[1] += 10;
+push(numbers, 9);
+
+print numbers; // [30, 11, 4, 1, 5, 9]
+print alias == numbers; // true
+print len(alias); // 6
+
+let mut sum = 0;
+for n in numbers {
+  sum += n;
+}
+print sum; // 60
+
+// A list can contain itself, and is shown as [...] inside of itself
+let cycle = [1];
+push(cycle, cycle);
+print cycle; // [1, [...]]
+
+let other = [1];
+push(other, other);
+print cycle == other; // true
+print cycle == [1, [2]]; // false
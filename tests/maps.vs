@@ -0,0 +1,15 @@
+let ages = ["alice": 31, "bob": 27];
+
+ages["carol"] = 40;
+ages["bob"] += 1;
+
+print ages; // ["alice": 31, "bob": 28, "carol": 40]
+
+for name in ages {
+  print name + " is " + ages[name];
+}
+
+// So can a map
+let node = ["name": "root"];
+node["self"] = node;
+print node; // ["name": "root", "self": [...]]
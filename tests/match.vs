@@ -0,0 +1,35 @@
+fn describe(x) {
+    return match x {
+        0 => "zero",
+        1 | 2 | 3 => "small",
+        -5..0 => "negative",
+        4..=10 => "medium",
+        "a" | "b" => "letter",
+        n if n == 500 => "big: {n}",
+        true => "yes",
+        null => "nothing",
+        _ => "other",
+    };
+}
+
+for value in [0, 2, -3, 10, 11, 500, "a", "z", true, null, 4.5] {
+    print describe(value);
+}
+
+for i in [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15] {
+    match [i % 3, i % 5] {
+        _ if i % 15 == 0 => { print "FizzBuzz"; }
+        _ if i % 3 == 0 => { print "Fizz"; }
+        _ if i % 5 == 0 => { print "Buzz"; }
+        _ => { print i; }
+    }
+}
+
+let grade = match 87 {
+    90..=100 => "A",
+    80..90 => "B",
+    _ => "C",
+};
+print grade;
+
+// match (1, 2) { (1, y) | (y, 1) => y } // Error: Cannot bind 'y' inside a pattern with '|'
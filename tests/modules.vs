@@ -0,0 +1,34 @@
+import "modules/geometry.vs";
+
+print geometry; // <module geometry>
+print geometry.pi; // 3.14
+print geometry.area(2); // 12.56
+print geometry.Shape.Circle(1); // Shape.Circle(1)
+
+// The module only runs once, so both names share its state
+import "modules/geometry.vs" as geo;
+print geo.increment(); // 1
+print geometry.increment(); // 2
+print geo == geometry; // true
+
+fn circle_area(shape) {
+    return match shape {
+        geo.Shape.Circle(r) => geo.area(r),
+        _ => null,
+    };
+}
+print circle_area(geometry.Shape.Circle(1)); // 3.14
+
+// Native functions work inside of a module, but aren't members of it
+print geometry.sides([1, 2, 3, 4]); // 4
+try {
+    print geometry.len;
+} catch e {
+    print e.message; // Module 'geometry' has no member 'len'
+}
+
+// geometry.radius; // Error: Module 'geometry' has no member 'radius'
+// import "modules/missing.vs"; // Error: Cannot import 'tests/modules/missing.vs'
+// import "modules/cycle_a.vs"; // Error: Import cycle: tests/modules/cycle_a.vs -> tests/modules/cycle_b.vs -> tests/modules/cycle_a.vs
+// import "modules/broken.vs"; // Error in broken.vs, then: Cannot import 'tests/modules/broken.vs' because it contains errors
+// import "modules/failing.vs"; // Error reported in failing.vs
@@ -0,0 +1,24 @@
+// Imported by modules.vs
+print "loading geometry";
+
+let pi = 3.14;
+
+fn area(r) {
+    return pi * r ^ 2;
+}
+
+enum Shape {
+    Circle(r),
+    Square(side),
+}
+
+let mut count = 0;
+
+fn sides(shape) {
+    return len(shape);
+}
+
+fn increment() {
+    count++;
+    return count;
+}
@@ -1,5 +1,5 @@
 fn makeCounter() {
-  let i = 0;
+  let mut i = 0;
   fn count() {
     i = i + 1;
     print i;
@@ -0,0 +1,19 @@
+print 3;
+print 3.0;
+print 7 / 2;
+print 6 / 2;
+print 2 ^ 10;
+print 2 ^ -1;
+print 1 + 0.5;
+print 1 == 1.0;
+print 20!;
+print 5.0!;
+
+let mut count = 0;
+count++;
+count += 2;
+print count;
+
+let values = [10, 20, 30];
+print values[len(values) - 1];
+print "Total: " + (1 + 2);
@@ -0,0 +1,31 @@
+for i in [1, 2, 3, 4, 5, 6] {
+    if i % 2 == 0 {
+        print "{i} is even";
+    }
+}
+
+print -7 % 3;
+print 7 ~/ 2;
+print -7 ~/ 2;
+
+print 6 & 3;
+print 6 | 3;
+print ~5;
+print 1 << 10;
+print -8 >> 1;
+
+print -2 ^ 2;
+print 2 ^ 3 ^ 2;
+print 2 * 3 ^ 2;
+
+let mut flags = 0;
+flags |= 1 << 3;
+flags |= 1;
+print flags;
+flags &= ~1;
+print flags;
+
+let mut seconds = 200;
+let minutes = seconds ~/ 60;
+seconds %= 60;
+print "{minutes}m {seconds}s";
@@ -0,0 +1,109 @@
+// Parameters can have default values
+fn greet(name, greeting = "hi") {
+    return greeting + " " + name
+}
+print greet("bob") // hi bob
+print greet("bob", "hey") // hey bob
+
+// Defaults are evaluated on every call and can use the parameters before them
+fn rectangle(width, height = width) {
+    return [width, height]
+}
+print rectangle(2) // [2, 2]
+print rectangle(2, 3) // [2, 3]
+
+fn append(value, list = []) {
+    push(list, value)
+    return list
+}
+print append(1) // [1]
+print append(2) // [2]
+
+// A rest parameter collects the remaining arguments into a list
+fn sum(...numbers) {
+    let mut total = 0
+    for n in numbers {
+        total += n
+    }
+    return total
+}
+print sum() // 0
+print sum(1, 2, 3) // 6
+
+fn tag(name, ...values) {
+    return name + ": " + len(values)
+}
+print tag("none") // none: 0
+print tag("some", 1, 2) // some: 2
+
+// Arguments can be given by the name of their parameter, after the positional ones
+fn point(x = 0, y = 0, z = 0) {
+    return [x, y, z]
+}
+print point(y: 2) // [0, 2, 0]
+print point(1, z: 3) // [1, 0, 3]
+print point(z: 3, x: 1) // [1, 0, 3]
+
+class Person {
+    fn init(name, age = null) {
+        this.name = name
+        this.age = age
+    }
+}
+let person = new Person(name: "alice", age: 30)
+print person.name // alice
+print person.age // 30
+
+let scale = fn(x, factor = 2) => x * factor
+print scale(4) // 8
+print scale(4, factor: 3) // 12
+
+// Defaults work with tail calls
+fn count_down(n, steps = 0) {
+    if n == 0 {
+        return steps
+    }
+    return count_down(n - 1, steps + 1)
+}
+print count_down(10000) // 10000
+
+// Arity errors describe what's accepted
+try {
+    greet()
+} catch e {
+    print e.message // Expected 1 to 2 arguments but received 0
+}
+
+try {
+    tag()
+} catch e {
+    print e.message // Expected at least 1 argument but received 0
+}
+
+try {
+    point(w: 1)
+} catch e {
+    print e.message // <fn point> has no parameter named 'w'
+}
+
+try {
+    point(1, x: 2)
+} catch e {
+    print e.message // Parameter 'x' was given more than one argument
+}
+
+try {
+    rectangle(height: 2)
+} catch e {
+    print e.message // Missing argument for parameter 'width' of <fn rectangle>
+}
+
+try {
+    len(value: [])
+} catch e {
+    print e.message // <native fn len> doesn't take named arguments
+}
+
+// fn f(a = 1, b) {} // Error: Parameter 'b' needs a default value, because the parameters before it have one
+// fn f(...a, b) {} // Error: Expected ')' after function parameters
+// point(x: 1, 2) // Error: Positional arguments can't come after named arguments
@@ -0,0 +1,71 @@
+// The pipeline operator and partial application
+
+fn double(x) {
+    return x * 2
+}
+
+fn add(a, b) {
+    return a + b
+}
+
+// x |> f is f(x), and x |> f(y) is f(x, y)
+print 5 |> double // 10
+print 5 |> add(1) // 6
+print 3 |> double |> add(4) |> double // 20
+
+// A pipeline can go on over several lines
+let total = [1, 2, 3]
+    |> len
+    |> add(10)
+print total // 13
+
+// The pipeline binds looser than the other operators
+print 1 + 2 |> double // 6
+
+let wrap = fn(x) => [x]
+print false and true |> wrap // [false]
+print 2 > 1 ? "big" : "small" |> wrap // ["big"]
+
+// '_' in the arguments of a call leaves them to be given later
+let add_one = add(_, 1)
+print add_one(41) // 42
+
+fn greet(greeting, name, punctuation = "!") {
+    return greeting + ", " + name + punctuation
+}
+
+let hello = greet("Hello", _)
+print hello("Ada") // Hello, Ada!
+print hello("Ada", "?") // Hello, Ada?
+print greet(_, "Bob", punctuation: ".")("Hi") // Hi, Bob.
+
+// Piping into a partial function fills its placeholder
+fn divide(a, b) {
+    return a ~/ b
+}
+
+print 100 |> divide(_, 5) // 20
+print 100 |> divide(5, _) // 0
+
+// Partial functions work with lambdas and native functions too
+let xs = []
+let add_to_xs = push(xs, _)
+add_to_xs(1)
+2 |> add_to_xs
+print xs // [1, 2]
+
+let scale = fn(x, factor) => x * factor
+print 4 |> scale(_, 3) // 12
+
+// The arity is what's left to be given
+try {
+    add_one(1, 2)
+} catch e {
+    print e.message // Expected 1 argument but received 2
+}
+
+try {
+    add(_, 1, 2)
+} catch e {
+    print e.message // Expected 2 arguments but received 3
+}
@@ -0,0 +1,62 @@
+// start..end doesn't include the end, start..=end does
+for i in 0..3 {
+    print i // 0, 1, 2
+}
+
+for i in 1..=3 {
+    print i // 1, 2, 3
+}
+
+print 0..10 // 0..10
+print 0..=10 step 2 // 0..=10 step 2
+
+// The step can be negative to count down
+for i in 10..0 step -3 {
+    print i // 10, 7, 4, 1
+}
+
+let n = 4
+for i in 0..n * 2 step n {
+    print i // 0, 4
+}
+
+// A range is never turned into a list, so it can be huge
+let big = 0..1000000000
+print len(big) // 1000000000
+print len(0..=10 step 3) // 4
+print len(5..0) // 0
+print len(0..9223372036854775807) // 9223372036854775807
+
+// A range can have more values than an int can count
+try {
+    len(-9223372036854775807..9223372036854775807)
+} catch e {
+    print e.message // Integer overflow: The range -9223372036854775807..9223372036854775807 has too many values for an int
+}
+
+for i in big {
+    if i == 2 {
+        break
+    }
+    print i // 0, 1
+}
+
+// in tests if a value is in a range, list, map or string
+print 5 in 0..10 // true
+print 10 in 0..10 // false
+print 10 in 0..=10 // true
+print 3 in 0..10 step 2 // false
+print 4 in 0..10 step 2 // true
+print 4.0 in 0..10 // true
+print "a" in 0..10 // false
+
+print 2 in [1, 2, 3] // true
+print "bob" in ["alice": 1] // false
+print "ell" in "hello" // true
+
+print 0..10 == 0..10 // true
+print 0..10 == 0..=10 // false
+
+// 0..1.5; // Error: Ranges can only be made of ints, but got float
+// 0..10 step 0; // Error: The step of a range can't be 0
+// 1 in 5; // Error: 'in' is not defined for int and int
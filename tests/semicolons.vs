@@ -0,0 +1,99 @@
+// A newline ends a statement, so semicolons are optional
+let a = 1
+let mut b = 2
+b += a
+print b // 3
+
+// Semicolons still separate statements on the same line
+print a; print b // 1, then 3
+
+fn add(x, y) {
+    return x + y
+}
+print add(1, 2) // 3
+
+// A line that ends in an operator, a comma or an open bracket continues on the next
+let sum = 1 +
+    2 +
+    3
+print sum // 6
+
+let list = [
+    1,
+    2,
+    3
+]
+print list // [1, 2, 3]
+
+print add(
+    10,
+    20
+) // 30
+
+let map = [
+    "x": 1,
+    "y": 2
+]
+print map["y"] // 2
+
+// Lines starting with '.' continue the line before
+class Counter {
+    fn init() {
+        this.count = 0
+    }
+
+    fn increment() {
+        this.count++
+        return this
+    }
+}
+let counter = new Counter()
+counter
+    .increment()
+    .increment()
+print counter.count // 2
+
+// else, catch and finally can start a new line
+if a > 5 {
+    print "big"
+}
+else {
+    print "small" // small
+}
+
+try {
+    throw "oops"
+}
+catch e {
+    print e.message // oops
+}
+
+// Lambdas inside of parentheses can still have several lines
+fn apply(f) {
+    return f(2)
+}
+print apply(fn(x) {
+    let doubled = x * 2
+    return doubled + 1
+}) // 5
+
+enum Shape {
+    Circle(r)
+    Square(side)
+}
+
+print match Shape.Square(3) {
+    Shape.Circle(r) => 3 * r ^ 2
+    Shape.Square(side) => side * side
+} // 9
+
+let mut i = 0
+while i < 2 {
+    i++
+}
+print i // 2
+
+fn nothing() {
+    return
+}
+print nothing() // null
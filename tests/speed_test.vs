@@ -7,7 +7,7 @@ fn fib(n) {
   return fib(n - 2) + fib(n - 1);
 }
 
-let i = 0;
+let mut i = 0;
 while i < iteration_count {
   i = i + 1;
   print fib(i);
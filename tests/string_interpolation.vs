@@ -0,0 +1,14 @@
+let name = "Alice";
+let age = 30;
+
+print "Hello {name}, you are {age + 1}";
+print "{name}";
+print "{{not interpolated}}";
+
+let scores = ["Alice": [9, 7]];
+print "Scores: {scores["Alice"]}";
+print "Nested: {"inner {name}"}";
+
+for i in [1, 2, 3] {
+    print "{i} squared is {i * i}";
+}
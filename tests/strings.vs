@@ -0,0 +1,9 @@
+print "Columns:\tname\tage\nRows:\t\"Alice\"\t30";
+print "Back\\slash and \{braces\}";
+print "Unicode: \u{1F600} caf\u{e9}";
+
+print r"C:\Users\{name}\n";
+print r#"She said "hi""#;
+
+print "Spans
+two lines";
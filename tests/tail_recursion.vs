@@ -0,0 +1,38 @@
+// Calls in tail position don't grow the stack, so these recurse a million
+// times without overflowing it
+
+fn count_down(n) {
+  if n == 0 {
+    return "done";
+  }
+  return count_down(n - 1);
+}
+
+print count_down(1000000); // done
+
+fn is_even(n) {
+  if n == 0 {
+    return true;
+  }
+  return is_odd(n - 1);
+}
+
+fn is_odd(n) {
+  if n == 0 {
+    return false;
+  }
+  return is_even(n - 1);
+}
+
+print is_even(1000000); // true
+
+// Calls that aren't in tail position still grow the stack, but a release
+// build has room for a few thousand of them
+fn count_up(n) {
+  if n == 0 {
+    return 0;
+  }
+  return 1 + count_up(n - 1);
+}
+
+print count_up(3500); // 3500
@@ -0,0 +1,79 @@
+// Tuples, multiple return values and destructuring
+
+let pair = (1, "two")
+print pair // (1, "two")
+print pair[0] // 1
+print pair[1] // two
+print len(pair) // 2
+print () // ()
+print (5,) // (5,)
+print (5) // 5
+
+print (1, 2) == (1, 2) // true
+print (1, 2) == (2, 1) // false
+print 2 in (1, 2, 3) // true
+
+for x in (3, 4) {
+    print x // 3, then 4
+}
+
+fn divmod(a, b) {
+    return (a - a % b) / b, a % b
+}
+
+print divmod(7, 2) // (3.0, 1)
+
+let (q, r) = divmod(7, 2)
+print q // 3.0
+print r // 1
+
+let mut (a, b) = (1, 2)
+let (_, second) = [10, 20]
+print second // 20
+
+fn swap() {
+    let (x, y) = (b, a)
+    a = x
+    b = y
+}
+swap()
+print a // 2
+print b // 1
+
+let positions = [(0, 0): "origin", (1, 0): "east"]
+print positions[(1, 0)] // east
+
+fn describe(point) {
+    return match point {
+        (0, 0) => "origin",
+        (x, 0) => "on the x axis at " + x,
+        (0, y) => "on the y axis at " + y,
+        (x, y) if x == y => "on the diagonal",
+        _ => "somewhere else"
+    }
+}
+
+print describe((0, 0)) // origin
+print describe((3, 0)) // on the x axis at 3
+print describe((0, 4)) // on the y axis at 4
+print describe((2, 2)) // on the diagonal
+print describe((1, 2, 3)) // somewhere else
+
+try {
+    let (x, y) = (1, 2, 3)
+} catch e {
+    print e.message // Cannot destructure a tuple of 3 values into 2 names
+    print e.line // 63
+}
+
+try {
+    let (x, y) = 5
+} catch e {
+    print e.message // Cannot destructure a int: Only tuples and lists can be destructured
+}
+
+try {
+    pair[0] = 3
+} catch e {
+    print e.message // Cannot assign to an index of a tuple
+}